@@ -0,0 +1,39 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rand::{rngs::StdRng, SeedableRng};
+use std::io::Cursor;
+
+// The game is a binary crate, so the target builds the engine and the ui straight from their sources.
+#[allow(dead_code)]
+#[path = "../../src/game/mod.rs"]
+mod game;
+#[allow(dead_code)]
+#[path = "../../src/ui/mod.rs"]
+mod ui;
+
+use crate::{
+    game::{Game, NumPlayers},
+    ui::{lang::Lang, style::Style, Ui},
+};
+
+// Run with `cargo fuzz run ui_play` from the repository root.
+fuzz_target!(|data: &[u8]| {
+    // The first byte picks the deal and the rest is typed at the prompt.
+    let (seed, input) = match data.split_first() {
+        Some((&seed, input)) => (seed, input),
+        None => return,
+    };
+
+    let mut ui = Ui {
+        input: Cursor::new(input),
+        output: Vec::new(),
+        paranoid: true,
+        record: None,
+        style: Style::Plain,
+        lang: Lang::English,
+    };
+    let game = Game::with_rng(NumPlayers::Three, &mut StdRng::seed_from_u64(u64::from(seed)));
+    // Running out of input halfway through a prompt is an error, but never a panic.
+    drop(ui.play(game));
+});
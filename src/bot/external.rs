@@ -0,0 +1,98 @@
+use std::{
+    io::{self, BufRead, BufReader, ErrorKind, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+use crate::game::{action::Action, Game};
+
+use super::Strategy;
+
+/// A bot living in another process, spoken to over its stdin and stdout.
+///
+/// For every decision the bot is sent the position followed by the numbered legal actions:
+///
+/// ```text
+/// player 1
+/// identity Blue
+/// hand 0: [① (1) Red] 1: [② (3) Blue]
+/// common 12
+/// discard 0: [① (1) Green]
+/// Bi 0: [② (3) Purple] ...
+/// ...
+/// monads 12 0 1
+/// actions 3
+/// 0 draw
+/// 1 trade 0 1
+/// 2 end
+/// ```
+///
+/// and must answer with a single line holding the number of the action it picks. Players are
+/// numbered from 1, as they are at the table, and actions from 0.
+pub struct ExternalBot {
+    child: Child,
+    input: ChildStdin,
+    output: BufReader<ChildStdout>,
+}
+
+impl ExternalBot {
+    pub fn spawn(command: &str) -> io::Result<Self> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let input = child.stdin.take().unwrap();
+        let output = BufReader::new(child.stdout.take().unwrap());
+
+        Ok(ExternalBot { child, input, output })
+    }
+
+    fn send_position(&mut self, game: &Game, actions: &[Action]) -> io::Result<()> {
+        let player = &game.players[game.turn];
+        let table = &game.table;
+
+        writeln!(self.input, "player {}", game.turn + 1)?;
+        writeln!(self.input, "identity {}", player.identity)?;
+        writeln!(self.input, "hand {}", player.hand)?;
+        writeln!(self.input, "common {}", table.common.len())?;
+        writeln!(self.input, "discard {}", table.discard)?;
+        for value in table.ladder.values().skip(1) {
+            writeln!(self.input, "{:?} {}", value, table.deck(value))?;
+        }
+        write!(self.input, "monads {}", table.monad.len())?;
+        for player in &game.players {
+            write!(self.input, " {}", player.monads.len())?;
+        }
+        writeln!(self.input)?;
+
+        writeln!(self.input, "actions {}", actions.len())?;
+        for (i, action) in actions.iter().enumerate() {
+            writeln!(self.input, "{} {}", i, action)?;
+        }
+        self.input.flush()
+    }
+}
+
+impl Strategy for ExternalBot {
+    fn choose(&mut self, game: &Game, actions: &[Action]) -> io::Result<usize> {
+        self.send_position(game, actions)?;
+
+        let mut line = String::new();
+        if self.output.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(ErrorKind::UnexpectedEof, "external bot hung up"));
+        }
+
+        line.trim()
+            .parse()
+            .map_err(|_| io::Error::new(ErrorKind::InvalidData, format!("external bot sent {:?}", line.trim())))
+    }
+}
+
+impl Drop for ExternalBot {
+    fn drop(&mut self) {
+        drop(self.child.kill());
+        drop(self.child.wait());
+    }
+}
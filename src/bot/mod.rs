@@ -0,0 +1,143 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{fmt, io, str::FromStr};
+
+use crate::game::{action::Action, Game, ParseError};
+
+pub mod external;
+
+use self::external::ExternalBot;
+
+/// Something that can play a turn of Monad without a human at the keyboard.
+pub trait Strategy {
+    /// Picks one of `actions`, which are all legal for `game.turn` right now.
+    fn choose(&mut self, game: &Game, actions: &[Action]) -> io::Result<usize>;
+}
+
+/// Plays a uniformly random legal action.
+pub struct RandomBot(StdRng);
+
+impl Strategy for RandomBot {
+    fn choose(&mut self, _: &Game, actions: &[Action]) -> io::Result<usize> {
+        Ok(self.0.gen_range(0, actions.len()))
+    }
+}
+
+/// Plays whichever action gains the most points right away, grabbing monads whenever it can.
+pub struct GreedyBot;
+
+impl GreedyBot {
+    fn score(game: &Game, action: &Action) -> i64 {
+        match game.preview(game.turn, action) {
+            Ok(preview) => {
+                preview.monads as i64 * 1000
+                    + preview.points_after as i64
+                    - preview.points_before as i64
+            },
+            Err(_) => i64::MIN,
+        }
+    }
+}
+
+impl Strategy for GreedyBot {
+    fn choose(&mut self, game: &Game, actions: &[Action]) -> io::Result<usize> {
+        if let Some(end) = actions.iter().position(|action| matches!(action, Action::End)) {
+            // Only keep going while there is something worth doing.
+            if actions.iter().all(|action| GreedyBot::score(game, action) <= 0) {
+                return Ok(end);
+            }
+        }
+
+        let mut best = 0;
+        let mut best_score = i64::MIN;
+        for (i, action) in actions.iter().enumerate() {
+            let score = GreedyBot::score(game, action);
+            if score > best_score {
+                best = i;
+                best_score = score;
+            }
+        }
+
+        Ok(best)
+    }
+}
+
+/// A strategy that can be entered into a tournament, as named on the command line.
+#[derive(Clone)]
+pub enum BotSpec {
+    Random,
+    Greedy,
+    External(String),
+}
+
+impl BotSpec {
+    pub fn build(&self, seed: u64) -> io::Result<Box<dyn Strategy>> {
+        Ok(match self {
+            BotSpec::Random => Box::new(RandomBot(StdRng::seed_from_u64(seed))),
+            BotSpec::Greedy => Box::new(GreedyBot),
+            BotSpec::External(command) => Box::new(ExternalBot::spawn(command)?),
+        })
+    }
+}
+
+impl FromStr for BotSpec {
+    type Err = ParseError;
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            "random" => Ok(BotSpec::Random),
+            "greedy" => Ok(BotSpec::Greedy),
+            _ if source.starts_with("ext:") && source.len() > 4 => {
+                Ok(BotSpec::External(source[4..].to_string()))
+            },
+            _ => Err(ParseError::new("bot (random, greedy or ext:<command>)", source)),
+        }
+    }
+}
+
+impl fmt::Display for BotSpec {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BotSpec::Random => write!(fmt, "random"),
+            BotSpec::Greedy => write!(fmt, "greedy"),
+            BotSpec::External(command) => write!(fmt, "ext:{}", command),
+        }
+    }
+}
+
+/// Lets `bots` play `game` out, `bots[i]` sitting in seat `i`. Bots that might never finish need
+/// a game with a turn limit.
+pub fn play(mut game: Game, bots: &mut [Box<dyn Strategy>]) -> io::Result<Game> {
+    while !game.is_over() {
+        let player = game.turn;
+        // Ending the turn is always allowed when nothing else is, so there is something to choose.
+        let actions = game.legal_actions();
+        let choice = bots[player].choose(&game, &actions)?;
+        let action = actions.get(choice).ok_or_else(|| {
+            let message = format!("player {} picked action {} of {}", player + 1, choice, actions.len());
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        })?;
+        drop(game.perform(action.clone()));
+    }
+
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::NumPlayers;
+
+    #[test]
+    fn external_bots_see_the_seat_and_cant_pick_past_the_actions() {
+        let game = Game::with_rng(NumPlayers::Two, &mut StdRng::seed_from_u64(0));
+        let actions = game.legal_actions();
+
+        // Seats are numbered from 1, as the protocol shows, so this bot picks the first action.
+        let mut bot = ExternalBot::spawn("read word seat; echo $((seat - 1)); cat > /dev/null").unwrap();
+        assert_eq!(bot.choose(&game, &actions).ok(), Some(0));
+
+        let always = "while read line; do case \"$line\" in actions*) echo 99;; esac; done";
+        let mut bots: Vec<Box<dyn Strategy>> = vec![Box::new(ExternalBot::spawn(always).unwrap()), Box::new(GreedyBot)];
+        let error = play(game, &mut bots).err();
+        assert_eq!(error.map(|error| error.kind()), Some(io::ErrorKind::InvalidData));
+    }
+}
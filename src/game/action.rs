@@ -0,0 +1,385 @@
+use itertools::Itertools;
+use std::{error, fmt};
+
+use super::{
+    card::{Card, Value, ValueOrMonad},
+    invariant::InvariantError,
+    payment::PaymentPreference,
+    team::PassError,
+    BuyError,
+    DrawError,
+    FlipError,
+    Game,
+    LeapError,
+    TradeError,
+};
+
+#[derive(Clone)]
+pub enum Action {
+    Draw,
+    Flip,
+    Trade { card1: usize, card2: usize, bonus: bool },
+    Buy { cards: Vec<usize>, target: ValueOrMonad },
+    Leap { cards: Vec<usize> },
+    /// Hand a card to your partner, when playing in teams.
+    Pass { card: usize },
+    End,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::Draw => write!(fmt, "draw"),
+            Action::Flip => write!(fmt, "flip"),
+            Action::Trade { card1, card2, bonus } => {
+                write!(fmt, "trade {} {}{}", card1, card2, if *bonus { " bonus" } else { "" })
+            },
+            Action::Buy { cards, target } => {
+                write!(fmt, "buy {} -> ", cards.iter().join(" "))?;
+                match target {
+                    ValueOrMonad::Value(value) => write!(fmt, "{:?}", value),
+                    ValueOrMonad::Monad => write!(fmt, "Monad"),
+                }
+            },
+            Action::Leap { cards } => write!(fmt, "leap {}", cards.iter().join(" ")),
+            Action::Pass { card } => write!(fmt, "pass {}", card),
+            Action::End => write!(fmt, "end"),
+        }
+    }
+}
+
+/// What an action gained the player: cards are always pushed onto the end of the hand.
+pub struct Outcome {
+    pub cards: usize,
+    pub monads: usize,
+    /// Cards an expansion card let the player see the top of their decks.
+    pub revealed: Vec<Card>,
+}
+
+#[derive(Debug)]
+pub enum ActionError {
+    GameOver,
+    AlreadyActed,
+    CannotEnd,
+    Draw(DrawError),
+    Flip(FlipError),
+    Trade(TradeError),
+    Buy(BuyError),
+    Leap(LeapError),
+    Pass(PassError),
+    /// The action was carried out, but left the game in a state the rules can't produce. Only
+    /// checked in debug builds.
+    Broken(InvariantError),
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::ActionError::*;
+        match self {
+            GameOver => write!(fmt, "The game is over!"),
+            AlreadyActed => write!(fmt, "You already did something else this turn!"),
+            CannotEnd => write!(fmt, "You can't end your turn yet! You can still take an action."),
+            Draw(error) => write!(fmt, "{}", error),
+            Flip(error) => write!(fmt, "{}", error),
+            Trade(error) => write!(fmt, "{}", error),
+            Buy(error) => write!(fmt, "{}", error),
+            Leap(error) => write!(fmt, "{}", error),
+            Pass(error) => write!(fmt, "{}", error),
+            Broken(error) => write!(fmt, "That broke the game: {}", error),
+        }
+    }
+}
+
+impl error::Error for ActionError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        use self::ActionError::*;
+        match self {
+            Draw(error)   => Some(error),
+            Flip(error)   => Some(error),
+            Trade(error)  => Some(error),
+            Buy(error)    => Some(error),
+            Leap(error)   => Some(error),
+            Pass(error)   => Some(error),
+            Broken(error) => Some(error),
+            GameOver | AlreadyActed | CannotEnd => None,
+        }
+    }
+}
+
+impl From<DrawError> for ActionError {
+    fn from(error: DrawError) -> Self { ActionError::Draw(error) }
+}
+
+impl From<FlipError> for ActionError {
+    fn from(error: FlipError) -> Self { ActionError::Flip(error) }
+}
+
+impl From<TradeError> for ActionError {
+    fn from(error: TradeError) -> Self { ActionError::Trade(error) }
+}
+
+impl From<BuyError> for ActionError {
+    fn from(error: BuyError) -> Self { ActionError::Buy(error) }
+}
+
+impl From<LeapError> for ActionError {
+    fn from(error: LeapError) -> Self { ActionError::Leap(error) }
+}
+
+impl From<PassError> for ActionError {
+    fn from(error: PassError) -> Self { ActionError::Pass(error) }
+}
+
+/// Where a card leaving the hand ends up, as decided by `Table::return_card`.
+pub enum Destination {
+    Discard,
+    Bottom(Value),
+    Partner(usize),
+}
+
+impl fmt::Display for Destination {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Destination::Discard => write!(fmt, "the discard pile"),
+            Destination::Bottom(value) => write!(fmt, "the bottom of the {:?} deck", value),
+            Destination::Partner(partner) => write!(fmt, "your partner, player {}", partner + 1),
+        }
+    }
+}
+
+pub struct Preview {
+    pub drawn: Vec<Value>,
+    pub monads: usize,
+    pub returned: Vec<(Card, Destination)>,
+    pub points_before: usize,
+    pub points_after: usize,
+}
+
+impl fmt::Display for Preview {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if !self.drawn.is_empty() {
+            write!(fmt, "You will draw:")?;
+            for value in &self.drawn {
+                write!(fmt, " {:?}", value)?;
+            }
+            writeln!(fmt)?;
+        }
+        if self.monads > 0 {
+            writeln!(fmt, "You will gain {} monad(s)", self.monads)?;
+        }
+        for (card, destination) in &self.returned {
+            writeln!(fmt, "{} goes to {}", card, destination)?;
+        }
+        writeln!(fmt, "Hand points: {} -> {}", self.points_before, self.points_after)
+    }
+}
+
+impl Game {
+    /// Applies `action` for `player` without regard for whose turn it is.
+    pub fn act(&mut self, player: usize, action: Action) -> Result<Outcome, ActionError> {
+        let spent = match &action {
+            Action::Trade { card1, card2, .. } => vec![*card1, *card2],
+            Action::Buy { cards, .. } | Action::Leap { cards } => cards.clone(),
+            _ => Vec::new(),
+        };
+        let spent = spent
+            .into_iter()
+            .filter_map(|i| self.players[player].hand.get(i).cloned())
+            .collect::<Vec<_>>();
+
+        let (cards, monads) = match action {
+            Action::Draw => {
+                self.draw(player)?;
+                (1, 0)
+            },
+            Action::Flip => {
+                self.flip()?;
+                (0, 0)
+            },
+            Action::Trade { card1, card2, bonus } => {
+                let (cards, monad) = self.trade(player, card1, card2, bonus)?;
+                (cards, monad as usize)
+            },
+            Action::Buy { mut cards, target } => {
+                let (cards, monad) = self.buy(player, &mut cards, target)?;
+                (cards, monad as usize)
+            },
+            Action::Leap { mut cards } => {
+                self.leap(player, &mut cards)?;
+                (1, 0)
+            },
+            Action::Pass { card } => {
+                self.pass(player, card)?;
+                (0, 0)
+            },
+            Action::End => (0, 0),
+        };
+
+        Ok(Outcome { cards, monads, revealed: self.peeks(&spent) })
+    }
+
+    /// Applies `action` for the current player, following the turn structure: a turn is either a
+    /// single draw or flip, or any number of trades, buys, leaps and passes followed by an end. A
+    /// pass is an action like the others, so a player who has passed can no longer draw or flip. A
+    /// player with nothing they can do may end their turn straight away.
+    pub fn perform(&mut self, action: Action) -> Result<Outcome, ActionError> {
+        if self.is_over() {
+            return Err(ActionError::GameOver);
+        }
+
+        let player = self.turn;
+        let outcome = match action {
+            Action::Draw | Action::Flip => {
+                if self.acted {
+                    return Err(ActionError::AlreadyActed);
+                }
+                let outcome = self.act(player, action)?;
+                self.end_turn();
+                outcome
+            },
+            Action::End => {
+                if !self.can_end() {
+                    return Err(ActionError::CannotEnd);
+                }
+                self.end_turn();
+                Outcome { cards: 0, monads: 0, revealed: Vec::new() }
+            },
+            action => {
+                let outcome = self.act(player, action)?;
+                self.acted = true;
+                outcome
+            },
+        };
+
+        // Only actions that really happen are checked, not the copies `preview` acts on.
+        if cfg!(debug_assertions) {
+            self.validate().map_err(ActionError::Broken)?;
+        }
+
+        Ok(outcome)
+    }
+
+    fn end_turn(&mut self) {
+        self.turns_played += 1;
+        self.skip_to((self.turn + 1) % self.players.len());
+    }
+
+    /// Starts `player`'s turn straight away, as if everyone in between had passed.
+    pub fn skip_to(&mut self, player: usize) {
+        self.turn = player;
+        self.acted = false;
+        self.players[player].took_bonus = false;
+        self.players[player].passed = 0;
+    }
+
+    /// Whether the current player may end their turn: once they have acted, or when there is
+    /// nothing they can do.
+    pub fn can_end(&self) -> bool {
+        self.acted || self.check_player_end(self.turn) || self.playable_actions().is_empty()
+    }
+
+    /// Every action the current player could `perform` successfully right now. Buys are only
+    /// offered with their optimal payment, and leaps with the leftmost commons in the hand.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        if self.is_over() {
+            return Vec::new();
+        }
+
+        let mut legal = self.playable_actions();
+        let can_end = self.acted || self.check_player_end(self.turn) || legal.is_empty();
+        // Passing is never needed, so it doesn't stop a player with nothing else to do ending
+        // their turn.
+        legal.extend(
+            (0..self.players[self.turn].hand.len())
+                .map(|card| Action::Pass { card })
+                .filter(|action| self.preview(self.turn, action).is_ok()),
+        );
+        if can_end {
+            legal.push(Action::End);
+        }
+
+        legal
+    }
+
+    // Every legal action but ending the turn.
+    fn playable_actions(&self) -> Vec<Action> {
+        let player = &self.players[self.turn];
+        let mut candidates = Vec::new();
+
+        if !self.acted {
+            candidates.push(Action::Draw);
+            candidates.push(Action::Flip);
+        }
+
+        for (card1, card2) in (0..player.hand.len()).tuple_combinations() {
+            candidates.push(Action::Trade { card1, card2, bonus: false });
+            if player.can_take_bonus(card1, card2) {
+                candidates.push(Action::Trade { card1, card2, bonus: true });
+            }
+        }
+
+        let targets = self.table.ladder
+            .values()
+            .map(ValueOrMonad::Value)
+            .chain(Some(ValueOrMonad::Monad));
+        for target in targets {
+            if let Ok(cards) = self.optimal_payment(self.turn, target, PaymentPreference::keep_all()) {
+                candidates.push(Action::Buy { cards, target });
+            }
+        }
+
+        let commons = player.hand.find_all(Card::is_common);
+        for count in self.table.ladder.leaps() {
+            if commons.len() >= count {
+                candidates.push(Action::Leap { cards: commons[..count].to_vec() });
+            }
+        }
+
+        candidates
+            .into_iter()
+            .filter(|action| self.preview(self.turn, action).is_ok())
+            .collect()
+    }
+
+    /// Works out what `action` would do to `player`'s hand without touching the real game.
+    pub fn preview(&self, player: usize, action: &Action) -> Result<Preview, ActionError> {
+        let returned_indexes = match action {
+            Action::Trade { card1, card2, .. } => vec![*card1, *card2],
+            Action::Pass { card } => vec![*card],
+            Action::Buy { cards, .. } | Action::Leap { cards } => cards.clone(),
+            Action::Draw | Action::Flip | Action::End => Vec::new(),
+        };
+
+        let mut after = self.clone();
+        after.act(player, action.clone())?;
+
+        let before = &self.players[player];
+        let after = &after.players[player];
+        let kept = before.hand.len() - returned_indexes.len();
+        let passed_to = match action {
+            Action::Pass { .. } => self.partner(player),
+            _ => None,
+        };
+
+        let returned = returned_indexes
+            .into_iter()
+            .map(|i| {
+                let card = before.hand[i].clone();
+                let destination = match passed_to {
+                    Some(partner) => Destination::Partner(partner),
+                    None if card.is_common() => Destination::Discard,
+                    None => Destination::Bottom(card.value),
+                };
+                (card, destination)
+            })
+            .collect();
+
+        Ok(Preview {
+            drawn: after.hand[kept..].iter().map(|card| card.value).collect(),
+            monads: after.monads.len() - before.monads.len(),
+            returned,
+            points_before: before.hand.iter().map(Card::num).sum(),
+            points_after: after.hand.iter().map(Card::num).sum(),
+        })
+    }
+}
@@ -0,0 +1,261 @@
+use std::{error, fmt};
+
+use super::{
+    card::{self, Card, Color, ColorSet, Deck, Ladder, Value},
+    invariant::InvariantError,
+    player::Player,
+    rules::Rules,
+    table::{Table, MONADS},
+    team::TeamError,
+    Game,
+    NumPlayers,
+};
+
+#[derive(Debug)]
+pub enum BuildError {
+    NoSuchPlayer(usize),
+    NotOnLadder(Value),
+    NotInSet(Color),
+    IdentityTaken(Color),
+    Inconsistent(InvariantError),
+    Teams(TeamError),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::NoSuchPlayer(player) => write!(fmt, "There is no player {}", player + 1),
+            BuildError::NotOnLadder(value) => write!(fmt, "There are no {:?} cards in this game", value),
+            BuildError::NotInSet(color) => write!(fmt, "There are no {} cards in this game", color),
+            BuildError::IdentityTaken(color) => write!(fmt, "{} is more than one player's colour", color),
+            BuildError::Inconsistent(error) => write!(fmt, "Inconsistent position: {}", error),
+            BuildError::Teams(error) => write!(fmt, "{}", error),
+        }
+    }
+}
+
+impl error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            BuildError::Inconsistent(error) => Some(error),
+            BuildError::Teams(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Sets up an exact position instead of dealing one at random.
+///
+/// Anything left unspecified is filled in: cards not placed in a hand or an explicitly set deck go
+/// to their own deck (commons to the common deck, or the discard pile if the common deck was set),
+/// and monads no player holds stay in the pile. The expansion cards the rules mix in are filled in
+/// the same way. Decks list their cards bottom first, so the last card is the next one drawn.
+/// `build` checks the result with `Game::validate`.
+pub struct GameBuilder {
+    num_players: NumPlayers,
+    players: Vec<Player>,
+    decks: Vec<(Value, Vec<Card>)>,
+    discard: Option<Vec<Card>>,
+    monad_pile: Option<usize>,
+    turn: usize,
+    acted: bool,
+    turns_played: usize,
+    rules: Rules,
+    ladder: &'static Ladder,
+    colors: &'static ColorSet,
+    error: Option<BuildError>,
+}
+
+impl GameBuilder {
+    pub fn new(num_players: NumPlayers) -> Self {
+        GameBuilder {
+            num_players,
+            players: card::SIX_COLORS.colors().take(num_players as usize).map(Player::from).collect(),
+            decks: Vec::new(),
+            discard: None,
+            monad_pile: None,
+            turn: 0,
+            acted: false,
+            turns_played: 0,
+            rules: Rules::default(),
+            ladder: &card::STANDARD,
+            colors: &card::SIX_COLORS,
+            error: None,
+        }
+    }
+
+    fn player_mut(&mut self, player: usize) -> Option<&mut Player> {
+        if player >= self.players.len() {
+            self.error = Some(BuildError::NoSuchPlayer(player));
+        }
+        self.players.get_mut(player)
+    }
+
+    pub fn identity(mut self, player: usize, identity: Color) -> Self {
+        if let Some(player) = self.player_mut(player) {
+            player.identity = identity;
+        }
+        self
+    }
+
+    pub fn hand(mut self, player: usize, cards: Vec<Card>) -> Self {
+        if let Some(player) = self.player_mut(player) {
+            player.hand = Deck::from(cards);
+        }
+        self
+    }
+
+    pub fn monads(mut self, player: usize, count: usize) -> Self {
+        if let Some(player) = self.player_mut(player) {
+            player.monads = vec![card::Monad; count];
+        }
+        self
+    }
+
+    pub fn took_bonus(mut self, player: usize, took_bonus: bool) -> Self {
+        if let Some(player) = self.player_mut(player) {
+            player.took_bonus = took_bonus;
+        }
+        self
+    }
+
+    pub fn passed(mut self, player: usize, passed: usize) -> Self {
+        if let Some(player) = self.player_mut(player) {
+            player.passed = passed;
+        }
+        self
+    }
+
+    pub fn deck(mut self, value: Value, cards: Vec<Card>) -> Self {
+        self.decks.retain(|&(deck, _)| deck != value);
+        self.decks.push((value, cards));
+        self
+    }
+
+    pub fn discard(mut self, cards: Vec<Card>) -> Self {
+        self.discard = Some(cards);
+        self
+    }
+
+    pub fn monad_pile(mut self, count: usize) -> Self {
+        self.monad_pile = Some(count);
+        self
+    }
+
+    /// Whose turn it is, and whether they have already traded, bought or leapt this turn.
+    pub fn turn(mut self, player: usize, acted: bool) -> Self {
+        if player >= self.players.len() {
+            self.error = Some(BuildError::NoSuchPlayer(player));
+        }
+        self.turn = player;
+        self.acted = acted;
+        self
+    }
+
+    pub fn turns_played(mut self, turns: usize) -> Self {
+        self.turns_played = turns;
+        self
+    }
+
+    pub fn rules(mut self, rules: Rules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// The values cards can have. Cards passed in take their value from this ladder.
+    pub fn ladder(mut self, ladder: &'static Ladder) -> Self {
+        self.ladder = ladder;
+        self
+    }
+
+    /// The colours cards come in. Cards and identities passed in take their colour from this set.
+    pub fn colors(mut self, colors: &'static ColorSet) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    pub fn build(mut self) -> Result<Game, BuildError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let ladder = self.ladder;
+        let cards = self.players
+            .iter_mut()
+            .flat_map(|player| player.hand.iter_mut())
+            .chain(self.decks.iter_mut().flat_map(|(_, cards)| cards.iter_mut()))
+            .chain(self.discard.iter_mut().flatten());
+        let colors = self.colors;
+        for card in cards {
+            card.value = ladder.at(card.value).ok_or(BuildError::NotOnLadder(card.value))?;
+            card.color = colors.at(card.color).ok_or(BuildError::NotInSet(card.color))?;
+        }
+        for i in 0..self.players.len() {
+            let identity = colors.at(self.players[i].identity).ok_or(BuildError::NotInSet(self.players[i].identity))?;
+            if self.players[..i].iter().any(|player| player.identity == identity) {
+                return Err(BuildError::IdentityTaken(identity));
+            }
+            self.players[i].identity = identity;
+        }
+        if let Some(&(value, _)) = self.decks.iter().find(|&&(value, _)| ladder.at(value).is_none()) {
+            return Err(BuildError::NotOnLadder(value));
+        }
+
+        let sorted = Table::sorted(self.num_players, ladder, colors);
+        let mut leftovers: Vec<Card> = ladder
+            .values()
+            .flat_map(|value| sorted.deck(value).iter().cloned())
+            .collect();
+        for &special in &self.rules.expansion {
+            if let Some(value) = ladder.values().nth(special.rank) {
+                leftovers.extend(colors.colors().map(|color| Card { value, color, special: Some(special) }));
+            }
+        }
+        let placed = self.players
+            .iter()
+            .flat_map(|player| player.hand.iter())
+            .chain(self.decks.iter().flat_map(|(_, cards)| cards.iter()))
+            .chain(self.discard.iter().flatten());
+        for card in placed {
+            if let Some(i) = leftovers.iter().position(|leftover| leftover == card) {
+                leftovers.remove(i);
+            }
+        }
+
+        let set: Vec<Value> = self.decks.iter().map(|&(value, _)| value).collect();
+        let discard_set = self.discard.is_some();
+
+        let mut table = Table::empty(ladder, colors);
+        table.discard = Deck::from(self.discard.unwrap_or_default());
+        for (value, cards) in self.decks {
+            table.deck_mut(value).extend(cards);
+        }
+
+        // Whatever is missing from an explicitly set deck makes the position inconsistent,
+        // which `validate` reports below.
+        for card in leftovers {
+            if !set.contains(&card.value) {
+                table.deck_mut(card.value).push(card);
+            } else if card.is_common() && !discard_set {
+                table.discard.push(card);
+            }
+        }
+
+        let held: usize = self.players.iter().map(|player| player.monads.len()).sum();
+        let pile = self.monad_pile.unwrap_or_else(|| MONADS.saturating_sub(held));
+        table.monad = vec![card::Monad; pile];
+
+        let game = Game {
+            players: self.players,
+            table,
+            turn: self.turn,
+            acted: self.acted,
+            turns_played: self.turns_played,
+            rules: self.rules,
+        };
+        game.validate().map_err(BuildError::Inconsistent)?;
+        game.check_teams().map_err(BuildError::Teams)?;
+
+        Ok(game)
+    }
+}
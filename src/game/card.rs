@@ -1,162 +1,427 @@
-use rand::{seq::SliceRandom, thread_rng};
+use rand::{seq::SliceRandom, Rng};
 use std::{
+    cmp::Ordering,
     fmt,
-    ops::{Deref, DerefMut},
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut, RangeInclusive},
     str::FromStr,
 };
 
+use super::{expansion::Special, ParseError};
+
 #[must_use]
+#[derive(Clone, PartialEq)]
 pub struct Monad;
 
 impl FromStr for Monad {
-    type Err = ();
+    type Err = ParseError;
     fn from_str(source: &str) -> Result<Self, Self::Err> {
-        if source == "Monad" { Ok(Monad) } else { Err(()) }
+        if source == "Monad" { Ok(Monad) } else { Err(ParseError::new("monad", source)) }
     }
 }
 
-#[derive(PartialEq)]
-pub enum Temp {
-    Warm,
-    Cold,
+/// A temperature group, as an index into `ColorSet::groups`. Only cards of different
+/// temperatures trade together.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Temp(pub usize);
+
+/// One colour of a colour set.
+pub struct Hue {
+    pub name: &'static str,
+    /// The two letter name used by the move notation.
+    pub abbreviation: &'static str,
+    pub temp: Temp,
+    /// The colour it makes a bonus pair with, as an index into `ColorSet::hues`.
+    pub partner: usize,
+    /// The closest of the 256 colours terminals can show.
+    pub ansi: u8,
+    /// A symbol that tells it apart without seeing the colour.
+    pub glyph: &'static str,
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
-pub enum Color {
-    Red,
-    Orange,
-    Yellow,
-    Purple,
-    Blue,
-    Green,
+/// Every colour cards come in, and the temperature groups they fall into.
+pub struct ColorSet {
+    pub name: &'static str,
+    pub hues: &'static [Hue],
+    pub groups: &'static [&'static str],
+}
+
+const fn hue(
+    name: &'static str,
+    abbreviation: &'static str,
+    temp: usize,
+    partner: usize,
+    ansi: u8,
+    glyph: &'static str,
+) -> Hue {
+    Hue { name, abbreviation, temp: Temp(temp), partner, ansi, glyph }
+}
+
+pub const SIX_COLORS: ColorSet = ColorSet {
+    name: "standard",
+    hues: &[
+        hue("Red",    "Rd", 0, 3, 196, "♥"),
+        hue("Orange", "Or", 0, 4, 208, "♦"),
+        hue("Yellow", "Ye", 0, 5, 226, "★"),
+        hue("Purple", "Pu", 1, 0, 129, "♣"),
+        hue("Blue",   "Bl", 1, 1, 33,  "♠"),
+        hue("Green",  "Gr", 1, 2, 40,  "●"),
+    ],
+    groups: &["warm", "cold"],
+};
+
+/// The standard colours with a pink and teal bonus pair added.
+pub const EIGHT_COLORS: ColorSet = ColorSet {
+    name: "eight",
+    hues: &[
+        hue("Red",    "Rd", 0, 3, 196, "♥"),
+        hue("Orange", "Or", 0, 4, 208, "♦"),
+        hue("Yellow", "Ye", 0, 5, 226, "★"),
+        hue("Purple", "Pu", 1, 0, 129, "♣"),
+        hue("Blue",   "Bl", 1, 1, 33,  "♠"),
+        hue("Green",  "Gr", 1, 2, 40,  "●"),
+        hue("Pink",   "Pk", 0, 7, 213, "✿"),
+        hue("Teal",   "Tl", 1, 6, 37,  "■"),
+    ],
+    groups: &["warm", "cold"],
+};
+
+/// The standard colours split three ways, with the bonus pairs rearranged so each still trades.
+pub const THREE_GROUPS: ColorSet = ColorSet {
+    name: "three",
+    hues: &[
+        hue("Red",    "Rd", 0, 3, 196, "♥"),
+        hue("Orange", "Or", 0, 5, 208, "♦"),
+        hue("Yellow", "Ye", 2, 4, 226, "★"),
+        hue("Purple", "Pu", 1, 0, 129, "♣"),
+        hue("Blue",   "Bl", 1, 2, 33,  "♠"),
+        hue("Green",  "Gr", 2, 1, 40,  "●"),
+    ],
+    groups: &["warm", "cold", "earthy"],
+};
+
+pub const COLOR_SETS: [&ColorSet; 3] = [&SIX_COLORS, &EIGHT_COLORS, &THREE_GROUPS];
+
+impl PartialEq for ColorSet {
+    fn eq(&self, other: &ColorSet) -> bool { self.name == other.name }
+}
+
+impl ColorSet {
+    pub fn named(name: &str) -> Option<&'static ColorSet> {
+        COLOR_SETS.iter().cloned().find(|set| set.name == name)
+    }
+
+    pub fn colors(&'static self) -> impl Iterator<Item = Color> + Clone {
+        (0..self.hues.len()).map(move |index| Color { index, set: self })
+    }
+
+    /// The same colour in this set as `color` is in its own, if this set has it.
+    pub fn at(&'static self, color: Color) -> Option<Color> {
+        if color.index < self.hues.len() { Some(Color { index: color.index, set: self }) } else { None }
+    }
+
+    pub fn color(&'static self, name: &str) -> Option<Color> {
+        self.colors().find(|color| color.hue().name == name)
+    }
+
+    pub fn temps(&self) -> impl Iterator<Item = Temp> {
+        (0..self.groups.len()).map(Temp)
+    }
+
+    pub fn with_temp(&'static self, temp: Temp) -> Vec<Color> {
+        self.colors().filter(|color| color.temp() == temp).collect()
+    }
+}
+
+/// A colour of some colour set. Colours compare by where they come in their set, and every set
+/// lists the colours it shares with another in the same order.
+#[derive(Clone, Copy)]
+pub struct Color {
+    index: usize,
+    set: &'static ColorSet,
+}
+
+#[allow(non_upper_case_globals)]
+impl Color {
+    pub const Red: Color = Color { index: 0, set: &SIX_COLORS };
+    pub const Orange: Color = Color { index: 1, set: &SIX_COLORS };
+    pub const Yellow: Color = Color { index: 2, set: &SIX_COLORS };
+    pub const Purple: Color = Color { index: 3, set: &SIX_COLORS };
+    pub const Blue: Color = Color { index: 4, set: &SIX_COLORS };
+    pub const Green: Color = Color { index: 5, set: &SIX_COLORS };
+}
+
+impl PartialEq for Color {
+    fn eq(&self, other: &Color) -> bool { self.index == other.index }
+}
+
+impl Eq for Color {}
+
+impl Hash for Color {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.index.hash(state) }
+}
+
+impl Color {
+    pub fn hue(self) -> &'static Hue {
+        &self.set.hues[self.index]
+    }
+
+    pub fn set(self) -> &'static ColorSet {
+        self.set
+    }
+
+    /// Where the colour comes in its set.
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    pub fn temp(self) -> Temp {
+        self.hue().temp
+    }
+
+    pub fn bonus_partner(self) -> Color {
+        Color { index: self.hue().partner, set: self.set }
+    }
+
+    /// The two letter name used by the move notation.
+    pub fn abbreviation(self) -> &'static str {
+        self.hue().abbreviation
+    }
+}
+
+impl fmt::Debug for Color {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.hue().name)
+    }
 }
 
 impl fmt::Display for Color {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        write!(fmt, "{:?}", self)
+        write!(fmt, "{}", self.hue().name)
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
-pub enum Value {
-    Common,
-    Bi,
-    Tri,
-    Quad,
-    Quint,
+/// One rung of the value ladder.
+pub struct Tier {
+    pub name: &'static str,
+    /// The short name used by the move notation.
+    pub abbreviation: &'static str,
+    pub symbol: &'static str,
+    pub price: usize,
+    /// Cards of each colour in the deck, or `None` for one per player.
+    pub copies: Option<usize>,
+    /// How many commons leap straight to this tier, if any.
+    pub leap: Option<usize>,
 }
 
-impl fmt::Display for Value {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        use self::Value::*;
-        write!(fmt, "{}", match self {
-            Common => "① (1)",
-            Bi => "② (3)",
-            Tri => "③ (7)",
-            Quad => "④ (16)",
-            Quint => "⑤ (36)",
-        })
+/// Every value a card can have, from commons up, and what a monad costs on top. Two cards of the
+/// top tier trade for a monad.
+pub struct Ladder {
+    pub name: &'static str,
+    pub tiers: &'static [Tier],
+    pub monad_price: usize,
+}
+
+const fn tier(name: &'static str, symbol: &'static str, price: usize, leap: Option<usize>) -> Tier {
+    Tier { name, abbreviation: name, symbol, price, copies: Some(1), leap }
+}
+
+const COMMON: Tier = Tier { name: "Common", abbreviation: "Cm", symbol: "①", price: 1, copies: None, leap: None };
+
+pub const STANDARD: Ladder = Ladder {
+    name: "standard",
+    tiers: &[
+        COMMON,
+        tier("Bi",    "②", 3, None),
+        tier("Tri",   "③", 7, Some(4)),
+        tier("Quad",  "④", 16, Some(5)),
+        tier("Quint", "⑤", 36, Some(6)),
+    ],
+    monad_price: 80,
+};
+
+/// The standard ladder with a Sext on top, for longer games.
+pub const SIX_TIERS: Ladder = Ladder {
+    name: "six",
+    tiers: &[
+        COMMON,
+        tier("Bi",    "②", 3, None),
+        tier("Tri",   "③", 7, Some(4)),
+        tier("Quad",  "④", 16, Some(5)),
+        tier("Quint", "⑤", 36, Some(6)),
+        tier("Sext",  "⑥", 80, Some(7)),
+    ],
+    monad_price: 176,
+};
+
+pub const LADDERS: [&Ladder; 2] = [&STANDARD, &SIX_TIERS];
+
+impl PartialEq for Ladder {
+    fn eq(&self, other: &Ladder) -> bool { self.name == other.name }
+}
+
+impl Ladder {
+    pub fn named(name: &str) -> Option<&'static Ladder> {
+        LADDERS.iter().cloned().find(|ladder| ladder.name == name)
+    }
+
+    /// The ladder with a deck for each of `count` tiers above Common.
+    pub fn with_tiers(count: usize) -> Option<&'static Ladder> {
+        LADDERS.iter().cloned().find(|ladder| ladder.tiers.len() == count + 1)
+    }
+
+    pub fn values(&'static self) -> impl Iterator<Item = Value> + Clone {
+        (0..self.tiers.len()).map(move |rank| Value { rank, ladder: self })
+    }
+
+    /// The same rung in this ladder as `value` is in its own, if this ladder reaches that high.
+    pub fn at(&'static self, value: Value) -> Option<Value> {
+        if value.rank < self.tiers.len() { Some(Value { rank: value.rank, ladder: self }) } else { None }
+    }
+
+    pub fn common(&'static self) -> Value {
+        Value { rank: 0, ladder: self }
+    }
+
+    pub fn top(&'static self) -> Value {
+        Value { rank: self.tiers.len() - 1, ladder: self }
+    }
+
+    pub fn value(&'static self, name: &str) -> Option<Value> {
+        self.values().find(|value| value.tier().name == name)
+    }
+
+    pub fn price(&self, target: ValueOrMonad) -> usize {
+        match target {
+            ValueOrMonad::Value(value) => self.tiers[value.rank].price,
+            ValueOrMonad::Monad => self.monad_price,
+        }
+    }
+
+    /// What `commons` commons leap to, if anything.
+    pub fn leap(&'static self, commons: usize) -> Option<Value> {
+        self.values().find(|value| value.tier().leap == Some(commons))
+    }
+
+    /// How many commons a leap takes, at least and at most.
+    pub fn leaps(&self) -> RangeInclusive<usize> {
+        let counts = self.tiers.iter().filter_map(|tier| tier.leap);
+        counts.clone().min().unwrap_or(0)..=counts.max().unwrap_or(0)
     }
 }
 
-impl FromStr for Value {
-    type Err = ();
-    fn from_str(source: &str) -> Result<Self, Self::Err> {
-        use self::Value::*;
-        let value = match source {
-            "Common" => Common,
-            "Bi"     => Bi,
-            "Tri"    => Tri,
-            "Quad"   => Quad,
-            "Quint"  => Quint,
-            _ => return Err(()),
-        };
+/// A rung of some ladder. Values compare by how high they are, whatever ladder they come from.
+#[derive(Clone, Copy)]
+pub struct Value {
+    rank: usize,
+    ladder: &'static Ladder,
+}
+
+#[allow(non_upper_case_globals)]
+impl Value {
+    pub const Common: Value = Value { rank: 0, ladder: &STANDARD };
+    pub const Bi: Value = Value { rank: 1, ladder: &STANDARD };
+    pub const Tri: Value = Value { rank: 2, ladder: &STANDARD };
+    pub const Quad: Value = Value { rank: 3, ladder: &STANDARD };
+    pub const Quint: Value = Value { rank: 4, ladder: &STANDARD };
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool { self.rank == other.rank }
+}
+
+impl Eq for Value {}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> { Some(self.cmp(other)) }
+}
 
-        Ok(value)
+impl Ord for Value {
+    fn cmp(&self, other: &Value) -> Ordering { self.rank.cmp(&other.rank) }
+}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.rank.hash(state) }
+}
+
+impl fmt::Debug for Value {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.tier().name)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{} ({})", self.tier().symbol, self.tier().price)
     }
 }
 
 impl Value {
-    pub fn try_from(source: usize) -> Result<Self, ()> {
-        use self::Value::*;
-        let value = match source {
-            1 => Common,
-            2 => Bi,
-            3 => Tri,
-            4 => Quad,
-            5 => Quint,
-            _ => return Err(()),
-        };
-
-        Ok(value)
+    pub fn tier(self) -> &'static Tier {
+        &self.ladder.tiers[self.rank]
+    }
+    pub fn ladder(self) -> &'static Ladder {
+        self.ladder
+    }
+    /// How far up the ladder this is, with commons at 0.
+    pub fn rank(self) -> usize {
+        self.rank
     }
     pub fn succ(self) -> Option<Value> {
-        use self::Value::*;
-        match self {
-            Common => Some(Bi),
-            Bi     => Some(Tri),
-            Tri    => Some(Quad),
-            Quad   => Some(Quint),
-            Quint  => None,
-        }
+        self.ladder.values().nth(self.rank + 1)
     }
     pub fn prev(self) -> Option<Value> {
-        use self::Value::*;
-        match self {
-            Common => None,
-            Bi     => Some(Common),
-            Tri    => Some(Bi),
-            Quad   => Some(Tri),
-            Quint  => Some(Quad),
-        }
+        self.rank.checked_sub(1).map(|rank| Value { rank, ladder: self.ladder })
     }
     pub fn points(self) -> usize {
-        use self::Value::*;
-        match self {
-            Common => 1,
-            Bi     => 3,
-            Tri    => 7,
-            Quad   => 16,
-            Quint  => 36,
-        }
+        self.tier().price
     }
     pub fn is_common(self) -> bool {
-        self == Value::Common
+        self.rank == 0
+    }
+    /// The short name used by the move notation.
+    pub fn abbreviation(self) -> &'static str {
+        self.tier().abbreviation
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum ValueOrMonad { Value(Value), Monad }
 
-impl ValueOrMonad {
-    pub fn points(&self) -> usize {
-        match self {
-            ValueOrMonad::Value(value) => value.points(),
-            ValueOrMonad::Monad => 80,
-        }
-    }
-}
-
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct Card {
     pub value: Value,
     pub color: Color,
+    /// The expansion card this is, if it isn't a plain one.
+    pub special: Option<&'static Special>,
 }
 
 impl fmt::Display for Card {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        write!(fmt, "[{} {}]", self.value, self.color)
+        match self.special {
+            Some(special) => write!(fmt, "[{} {} {}]", self.value, self.color, special.name),
+            None => write!(fmt, "[{} {}]", self.value, self.color),
+        }
     }
 }
 
 impl Card {
-    pub fn temp(&self) -> Temp {
-        use self::Color::*;
-        match self.color {
-            Red | Orange | Yellow => Temp::Warm,
-            Purple | Blue | Green => Temp::Cold,
+    pub fn new(value: Value, color: Color) -> Self {
+        Card { value, color, special: None }
+    }
+
+    /// The colour the card counts as when traded together with `other`.
+    pub fn trade_color(&self, other: &Card) -> Color {
+        match self.special {
+            Some(special) => special.ability.trade_color(self, other),
+            None => self.color,
         }
     }
 
+    pub fn temp(&self) -> Temp {
+        self.color.temp()
+    }
+
     pub fn num(&self) -> usize {
         self.value.points()
     }
@@ -166,7 +431,7 @@ impl Card {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default, PartialEq)]
 pub struct Deck(Vec<Card>);
 
 impl fmt::Display for Deck {
@@ -174,18 +439,13 @@ impl fmt::Display for Deck {
         self
             .iter()
             .enumerate()
-            .map(|(i, card)| write!(fmt, "{}: {} ", i, card))
-            .collect()
+            .try_for_each(|(i, card)| write!(fmt, "{}: {} ", i, card))
     }
 }
 
 impl Deck {
-    pub fn multiple(multiple: usize) -> Self {
-        Deck(Vec::with_capacity(COLORS.len() * multiple))
-    }
-
-    pub fn shuffle(&mut self) {
-        self.0.shuffle(&mut thread_rng());
+    pub fn shuffle(&mut self, rng: &mut impl Rng) {
+        self.0.shuffle(rng);
     }
 
     pub fn find_all(&self, predicate: impl Fn(&Card) -> bool) -> Vec<usize> {
@@ -209,12 +469,3 @@ impl Deref for Deck {
 impl DerefMut for Deck {
     fn deref_mut(&mut self) -> &mut <Self as Deref>::Target { &mut self.0 }
 }
-
-pub const COLORS: [Color; 6] = [
-    Color::Red,
-    Color::Orange,
-    Color::Yellow,
-    Color::Purple,
-    Color::Blue,
-    Color::Green,
-];
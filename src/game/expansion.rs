@@ -0,0 +1,146 @@
+use rand::Rng;
+use std::fmt;
+
+use super::{
+    card::{Card, Color, Value},
+    table::Table,
+    Game,
+};
+
+/// What an expansion card does differently from a plain card of the same value and colour. The
+/// engine asks every special card taking part in a trade or buy, and every card spent by any action,
+/// leaps included, is asked what it reveals. Leaps need no hook of their own since they only take
+/// commons, and no special card is a common. Anything left at its default behaves like a plain card.
+/// Abilities are shared by every game, and so are `Sync`.
+pub trait Ability: Sync {
+    /// The colour `card` counts as when traded together with `other`.
+    fn trade_color(&self, card: &Card, _other: &Card) -> Color {
+        card.color
+    }
+
+    /// How many points to hand back, as commons, when `card` helped pay `overpaid` points more
+    /// than a buy cost.
+    fn refund(&self, _card: &Card, _overpaid: usize) -> usize {
+        0
+    }
+
+    /// The deck whose top card is shown to the player once `card` has been spent.
+    fn peek(&self, _card: &Card) -> Option<Value> {
+        None
+    }
+}
+
+/// A kind of expansion card. One of each colour is mixed into the deck of its value.
+pub struct Special {
+    pub name: &'static str,
+    /// The two letter name used by the move notation.
+    pub abbreviation: &'static str,
+    /// Where the cards go on the ladder, counting Common as 0.
+    pub rank: usize,
+    pub description: &'static str,
+    pub ability: &'static dyn Ability,
+}
+
+impl PartialEq for Special {
+    fn eq(&self, other: &Special) -> bool { self.name == other.name }
+}
+
+impl fmt::Debug for Special {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.name)
+    }
+}
+
+struct Prism;
+
+impl Ability for Prism {
+    // The bonus partner is always of another temperature, and makes a bonus pair too.
+    fn trade_color(&self, _card: &Card, other: &Card) -> Color {
+        other.color.bonus_partner()
+    }
+}
+
+struct Refund;
+
+impl Ability for Refund {
+    fn refund(&self, _card: &Card, overpaid: usize) -> usize {
+        overpaid
+    }
+}
+
+struct Peek;
+
+impl Ability for Peek {
+    fn peek(&self, card: &Card) -> Option<Value> {
+        card.value.succ()
+    }
+}
+
+pub const PRISM: Special = Special {
+    name: "Prism",
+    abbreviation: "Pr",
+    rank: 1,
+    description: "Trades as whichever colour suits the card it is traded with, even for a bonus.",
+    ability: &Prism,
+};
+
+pub const REFUND: Special = Special {
+    name: "Refund",
+    abbreviation: "Rf",
+    rank: 2,
+    description: "Gives change in commons when it helps pay more than a buy costs.",
+    ability: &Refund,
+};
+
+pub const PEEK: Special = Special {
+    name: "Peek",
+    abbreviation: "Pe",
+    rank: 1,
+    description: "Shows you the top card of the next deck up once you spend it.",
+    ability: &Peek,
+};
+
+pub const SPECIALS: [&Special; 3] = [&PRISM, &REFUND, &PEEK];
+
+impl Special {
+    pub fn named(name: &str) -> Option<&'static Special> {
+        SPECIALS.iter().cloned().find(|special| special.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn abbreviated(abbreviation: &str) -> Option<&'static Special> {
+        SPECIALS.iter().cloned().find(|special| special.abbreviation == abbreviation)
+    }
+}
+
+impl Table {
+    /// Shuffles one card of each colour of every kind in `specials` into the deck of its value.
+    pub fn mix_in(&mut self, specials: &[&'static Special], rng: &mut impl Rng) {
+        for &special in specials {
+            let value = match self.ladder.values().nth(special.rank) {
+                Some(value) => value,
+                None => continue,
+            };
+            for color in self.colors.colors() {
+                self.deck_mut(value).push(Card { value, color, special: Some(special) });
+            }
+            self.deck_mut(value).shuffle(rng);
+        }
+    }
+}
+
+impl Game {
+    /// Mixes `specials` into the decks and plays with them from now on.
+    pub fn add_expansion(&mut self, specials: Vec<&'static Special>, rng: &mut impl Rng) {
+        self.table.mix_in(&specials, rng);
+        self.rules.expansion = specials;
+    }
+
+    /// The top cards shown once `spent` have left the hand.
+    pub(super) fn peeks(&self, spent: &[Card]) -> Vec<Card> {
+        spent
+            .iter()
+            .filter_map(|card| card.special.and_then(|special| special.ability.peek(card)))
+            .filter_map(|value| self.table.deck(value).last().cloned())
+            .collect()
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt;
+
+use super::{
+    action::Action,
+    card::ValueOrMonad,
+    Game,
+};
+
+/// Something for one player to achieve, such as "get a Quint this turn".
+#[derive(Clone)]
+pub struct Goal {
+    /// A card of this value, or a monad, that the player doesn't have yet.
+    pub target: ValueOrMonad,
+    /// How many of the player's turns it may take, counting the current one.
+    pub turns: usize,
+    /// Whether drawing from the common deck is allowed on the way.
+    pub draws: bool,
+}
+
+impl Goal {
+    pub fn monad(turns: usize) -> Self {
+        Goal { target: ValueOrMonad::Monad, turns, draws: true }
+    }
+
+    fn count(&self, game: &Game, player: usize) -> usize {
+        let player = &game.players[player];
+        match self.target {
+            ValueOrMonad::Value(value) => player.hand.iter().filter(|card| card.value == value).count(),
+            ValueOrMonad::Monad => player.monads.len(),
+        }
+    }
+
+    /// Whether `player` has gained the target since `start`.
+    pub fn met(&self, start: &Game, game: &Game, player: usize) -> bool {
+        self.count(game, player) > self.count(start, player)
+    }
+
+    pub fn allows(&self, action: &Action) -> bool {
+        self.draws || !matches!(action, Action::Draw)
+    }
+}
+
+impl fmt::Display for Goal {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.target {
+            ValueOrMonad::Value(value) => write!(fmt, "Get a {:?}", value)?,
+            ValueOrMonad::Monad => write!(fmt, "Get a monad")?,
+        }
+        match self.turns {
+            1 => write!(fmt, " this turn")?,
+            turns => write!(fmt, " within {} turns", turns)?,
+        }
+        if !self.draws {
+            write!(fmt, " without drawing")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use rand::{seq::SliceRandom, Rng};
+use std::{error, fmt, str::FromStr};
+
+use super::{
+    card::{Color, ColorSet, Ladder},
+    player::Player,
+    table::Table,
+    Game,
+    NumPlayers,
+    ParseError,
+};
+
+/// How identity colours are handed out when a game is set up.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum Identities {
+    /// Any colours at all.
+    Random,
+    /// As many warm colours as cold ones, give or take one, in random seats.
+    #[default]
+    Balanced,
+    /// The same colours every time, alternating warm and cold around the table.
+    Seats,
+    /// The players pick for themselves, the last seat first.
+    Draft,
+}
+
+impl fmt::Display for Identities {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::Identities::*;
+        match self {
+            Random   => write!(fmt, "random"),
+            Balanced => write!(fmt, "balanced"),
+            Seats    => write!(fmt, "seats"),
+            Draft    => write!(fmt, "draft"),
+        }
+    }
+}
+
+impl FromStr for Identities {
+    type Err = ParseError;
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        use self::Identities::*;
+        match source {
+            "random"   => Ok(Random),
+            "balanced" => Ok(Balanced),
+            "seats"    => Ok(Seats),
+            "draft"    => Ok(Draft),
+            _ => Err(ParseError::new("way of handing out identities", source)),
+        }
+    }
+}
+
+// Deals the colours of each temperature group out in turn, a seat at a time.
+fn round_robin(groups: Vec<Vec<Color>>, count: usize) -> Vec<Color> {
+    let mut groups = groups.into_iter().map(Vec::into_iter).collect::<Vec<_>>();
+    let width = groups.len();
+    (0..).filter_map(|seat| groups[seat % width].next()).take(count).collect()
+}
+
+impl Identities {
+    /// Each seat's colour from `colors`, or `None` for a draft, which needs the players to choose.
+    pub fn assign(self, num_players: NumPlayers, colors: &'static ColorSet, rng: &mut impl Rng) -> Option<Vec<Color>> {
+        let count = num_players as usize;
+        let groups = colors.temps().map(|temp| colors.with_temp(temp)).collect::<Vec<_>>();
+        let colors = match self {
+            Identities::Random => {
+                let mut colors = colors.colors().collect::<Vec<_>>();
+                colors.shuffle(rng);
+                colors.truncate(count);
+                colors
+            },
+            Identities::Balanced => {
+                let mut groups = groups;
+                for group in &mut groups {
+                    group.shuffle(rng);
+                }
+                // Seats left over once every temperature has one go to random temperatures.
+                groups.shuffle(rng);
+                let mut colors = round_robin(groups, count);
+                colors.shuffle(rng);
+                colors
+            },
+            Identities::Seats => round_robin(groups, count),
+            Identities::Draft => return None,
+        };
+
+        Some(colors)
+    }
+}
+
+/// The seats in the order they pick in a draft: the last seat first, so whoever moves first
+/// picks last.
+pub fn draft_order(num_players: NumPlayers) -> impl Iterator<Item = usize> {
+    (0..num_players as usize).rev()
+}
+
+#[derive(Debug)]
+pub enum IdentityError {
+    Players(usize),
+    Taken(Color),
+    NoSuchColor(Color),
+}
+
+impl fmt::Display for IdentityError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::IdentityError::*;
+        match self {
+            Players(count)     => write!(fmt, "A game needs 2 to 4 players, not {}", count),
+            Taken(color)       => write!(fmt, "{} is already someone's colour", color),
+            NoSuchColor(color) => write!(fmt, "There are no {} cards in this game", color),
+        }
+    }
+}
+
+impl error::Error for IdentityError {}
+
+impl Game {
+    /// Deals a game on `ladder` in `colors` where seat `i` plays `identities[i]`, using `rng` for
+    /// every shuffle.
+    pub fn with_identities(
+        identities: &[Color],
+        ladder: &'static Ladder,
+        colors: &'static ColorSet,
+        rng: &mut impl Rng,
+    ) -> Result<Self, IdentityError> {
+        let num_players = NumPlayers::from_count(identities.len())
+            .map_err(|_| IdentityError::Players(identities.len()))?;
+        let mut players = Vec::new();
+        for (i, &color) in identities.iter().enumerate() {
+            if identities[..i].contains(&color) {
+                return Err(IdentityError::Taken(color));
+            }
+            players.push(Player::from(colors.at(color).ok_or(IdentityError::NoSuchColor(color))?));
+        }
+
+        let mut table = Table::new(num_players, ladder, colors, rng);
+
+        for player in &mut players {
+            player.hand.extend(table.common.drain(0..6));
+        }
+
+        Ok(Game { players, table, turn: 0, acted: false, turns_played: 0, rules: Default::default() })
+    }
+}
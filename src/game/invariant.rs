@@ -0,0 +1,114 @@
+use std::{collections::HashMap, error, fmt};
+
+use super::{
+    card::{Card, Color, Deck, Value},
+    table::MONADS,
+    Game,
+};
+
+#[derive(Debug)]
+pub enum InvariantError {
+    CardCount { value: Value, color: Color, expected: usize, found: usize },
+    MonadCount { expected: usize, found: usize },
+    WrongDeck { deck: &'static str, card: Card },
+    DuplicateSpecial(Card),
+    MissingSpecial(Card),
+    SpecialValue(Card),
+    NotInGame(Card),
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::InvariantError::*;
+        match self {
+            CardCount { value, color, expected, found } => write!(
+                fmt,
+                "Expected {} {:?} {} card(s) in play, found {}",
+                expected, value, color, found,
+            ),
+            MonadCount { expected, found } => write!(fmt, "Expected {} monads in play, found {}", expected, found),
+            WrongDeck { deck, card } => write!(fmt, "{} doesn't belong in the {} deck", card, deck),
+            DuplicateSpecial(card) => write!(fmt, "There is more than one {} in play", card),
+            MissingSpecial(card) => write!(fmt, "Expected a {} in play, found none", card),
+            SpecialValue(card) => write!(fmt, "{} has the wrong value for its kind", card),
+            NotInGame(card) => write!(fmt, "{} isn't a card of this game's ladder, colours or expansion", card),
+        }
+    }
+}
+
+impl error::Error for InvariantError {}
+
+impl Game {
+    /// Checks that no card or monad has been lost, duplicated or put in the wrong deck since the deal,
+    /// counting one of each expansion card the rules mix in.
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let table = &self.table;
+
+        let found = table.monad.len() + self.players.iter().map(|player| player.monads.len()).sum::<usize>();
+        if found != MONADS {
+            return Err(InvariantError::MonadCount { expected: MONADS, found });
+        }
+
+        let ladder = table.ladder;
+        let decks: Vec<(&'static str, &Deck, Value)> = ladder
+            .values()
+            .map(|value| (value.tier().name, table.deck(value), value))
+            .chain(Some(("discard", &table.discard, ladder.common())))
+            .collect();
+        for &(deck, cards, value) in &decks {
+            if let Some(card) = cards.iter().find(|card| card.value != value) {
+                return Err(InvariantError::WrongDeck { deck, card: card.clone() });
+            }
+        }
+
+        let mut counts: HashMap<(Value, Color), usize> = HashMap::new();
+        let mut specials: HashMap<(Value, Color, &str), usize> = HashMap::new();
+        let in_play = decks
+            .iter()
+            .flat_map(|&(_, cards, _)| cards.iter())
+            .chain(self.players.iter().flat_map(|player| player.hand.iter()));
+        for card in in_play {
+            let in_game = ladder.values().any(|value| value == card.value)
+                && table.colors.colors().any(|color| color == card.color)
+                && card.special.is_none_or(|special| self.rules.expansion.contains(&special));
+            if !in_game {
+                return Err(InvariantError::NotInGame(card.clone()));
+            }
+            match card.special {
+                Some(special) if card.value.rank() != special.rank => {
+                    return Err(InvariantError::SpecialValue(card.clone()));
+                },
+                Some(special) => *specials.entry((card.value, card.color, special.name)).or_insert(0) += 1,
+                None => *counts.entry((card.value, card.color)).or_insert(0) += 1,
+            }
+        }
+
+        for value in ladder.values() {
+            let expected = value.tier().copies.unwrap_or(self.players.len());
+            for color in table.colors.colors() {
+                let found = counts.get(&(value, color)).cloned().unwrap_or(0);
+                if found != expected {
+                    return Err(InvariantError::CardCount { value, color, expected, found });
+                }
+            }
+        }
+
+        // Expansion cards come on top of the plain ones, one of each kind in every colour.
+        for &special in &self.rules.expansion {
+            let value = match ladder.values().nth(special.rank) {
+                Some(value) => value,
+                None => continue,
+            };
+            for color in table.colors.colors() {
+                let card = Card { value, color, special: Some(special) };
+                match specials.get(&(value, color, special.name)) {
+                    None => return Err(InvariantError::MissingSpecial(card)),
+                    Some(&found) if found > 1 => return Err(InvariantError::DuplicateSpecial(card)),
+                    Some(_) => {},
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,17 +1,35 @@
 #![allow(dead_code)]
 extern crate itertools;
 use itertools::Itertools;
-use rand::{seq::SliceRandom, thread_rng};
-use std::{str::FromStr, fmt};
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use std::{error, fmt, str::FromStr};
 
 pub mod card;
 pub mod table;
 pub mod player;
+pub mod payment;
+pub mod action;
+pub mod invariant;
+pub mod builder;
+pub mod position;
+pub mod notation;
+pub mod record;
+pub mod solver;
+pub mod goal;
+pub mod rules;
+pub mod identity;
+pub mod team;
+pub mod expansion;
+
+#[cfg(test)]
+mod tests;
 
 use self::{
     table::Table,
-    card::{Card, Value, ValueOrMonad},
+    card::{Card, ColorSet, Ladder, Value, ValueOrMonad},
     player::Player,
+    rules::Rules,
+    identity::Identities,
 };
 
 #[derive(Clone, Copy)]
@@ -22,49 +40,132 @@ pub enum NumPlayers {
 }
 
 impl FromStr for NumPlayers {
-    type Err = ();
+    type Err = ParseError;
     fn from_str(source: &str) -> Result<Self, Self::Err> {
         source
             .trim()
             .parse::<usize>()
-            .map_err(|_| ())
-            .and_then(|num|
-                match num {
-                    2 => Ok(NumPlayers::Two),
-                    3 => Ok(NumPlayers::Three),
-                    4 => Ok(NumPlayers::Four),
-                    _ => Err(()),
-                }
-            )
+            .ok()
+            .and_then(|count| NumPlayers::from_count(count).ok())
+            .ok_or_else(|| ParseError::new("number of players from 2 to 4", source))
+    }
+}
+
+impl NumPlayers {
+    pub fn from_count(count: usize) -> Result<Self, ParseError> {
+        match count {
+            2 => Ok(NumPlayers::Two),
+            3 => Ok(NumPlayers::Three),
+            4 => Ok(NumPlayers::Four),
+            _ => Err(ParseError::new("number of players from 2 to 4", count)),
+        }
+    }
+}
+
+/// Text that doesn't name anything of the kind it was read as, such as a colour that no colour set
+/// has.
+#[derive(PartialEq, Debug)]
+pub struct ParseError {
+    /// What the text should have named.
+    pub expected: &'static str,
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new(expected: &'static str, found: impl ToString) -> Self {
+        ParseError { expected, found: found.to_string() }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "'{}' isn't a {}", self.found, self.expected)
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Debug)]
+pub enum SelectionError {
+    NoSuchCard(usize),
+    DuplicateCard(usize),
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::SelectionError::*;
+        match self {
+            NoSuchCard(index)    => write!(fmt, "There is no card {} in your hand", index),
+            DuplicateCard(index) => write!(fmt, "Card {} was selected more than once", index),
+        }
     }
 }
 
+impl error::Error for SelectionError {}
+
+/// Why nothing could be drawn: the common deck is empty, and the discard pile holds `discarded`
+/// cards that could be flipped instead.
+#[derive(Debug)]
+pub struct DrawError {
+    pub discarded: usize,
+}
+
+impl fmt::Display for DrawError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.discarded {
+            0         => write!(fmt, "No commons are left to draw or flip"),
+            discarded => write!(fmt, "The common deck is empty; flip the {} discarded card(s) first", discarded),
+        }
+    }
+}
+
+impl error::Error for DrawError {}
+
+#[derive(Debug)]
 pub enum FlipError {
     EmptyDiscardPile,
-    NonEmptyCommonDeck,
+    /// The common deck still holds this many cards.
+    NonEmptyCommonDeck(usize),
 }
 
 impl fmt::Display for FlipError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         use self::FlipError::*;
-        write!(fmt, "{}", match self {
-            EmptyDiscardPile => "Discard pile is empty",
-            NonEmptyCommonDeck => "Common deck still has cards",
-        })
+        match self {
+            EmptyDiscardPile          => write!(fmt, "Discard pile is empty"),
+            NonEmptyCommonDeck(count) => write!(fmt, "Common deck still has {} card(s)", count),
+        }
     }
 }
 
+impl error::Error for FlipError {}
+
+#[derive(Debug)]
 pub enum LeapError {
+    Selection(SelectionError),
     NumOfCards(usize),
-    NotAllCommons,
+    /// The first selected card that isn't a common.
+    NotAllCommons(usize),
+    OutOfCards(Value),
 }
 
 impl fmt::Display for LeapError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         use self::LeapError::*;
         match self {
-            NumOfCards(length) => write!(fmt, "Incorrect number of cards: {}", length),
-            NotAllCommons => write!(fmt, "Not all cards are common"),
+            Selection(error)     => write!(fmt, "{}", error),
+            NumOfCards(length)   => write!(fmt, "Incorrect number of cards: {}", length),
+            NotAllCommons(index) => write!(fmt, "Not all cards are common: card {} isn't", index),
+            OutOfCards(value)    => write!(fmt, "The {:?} deck is out of cards!", value),
+        }
+    }
+}
+
+impl error::Error for LeapError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            LeapError::Selection(error) => Some(error),
+            _ => None,
         }
     }
 }
@@ -74,90 +175,166 @@ pub enum TradeOutcome {
     Monad,
 }
 
+#[derive(Debug)]
 pub enum TradeError {
+    Selection(SelectionError),
     OutOfCards(Value),
-    SameTemperature,
-    NotSameValueOrIdentity,
+    OutOfMonads,
+    /// The two selected cards, which count as the same temperature.
+    SameTemperature(usize, usize),
+    /// The values of the two selected cards, neither of them wild.
+    NotSameValueOrIdentity(Value, Value),
+    NoBonus,
 }
 
 impl fmt::Display for TradeError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         use self::TradeError::*;
         match self {
-            OutOfCards(_) => write!(fmt, "Can't buy something of the same value"),
-            SameTemperature => write!(fmt, "Cards should not be the same tempurature"),
-            NotSameValueOrIdentity => write!(fmt, "Cards must have the same value, or one must match your color"),
+            Selection(error)       => write!(fmt, "{}", error),
+            OutOfCards(value)      => write!(fmt, "The {:?} deck is out of cards!", value),
+            OutOfMonads            => write!(fmt, "There are no monads left!"),
+            SameTemperature(first, other) => {
+                write!(fmt, "Cards {} and {} are the same temperature, and they should not be", first, other)
+            },
+            NotSameValueOrIdentity(first, other) => write!(
+                fmt,
+                "Cards must have the same value, or one must match your color, not {:?} and {:?}",
+                first, other,
+            ),
+            NoBonus                => write!(fmt, "Those cards don't earn a bonus"),
+        }
+    }
+}
+
+impl error::Error for TradeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            TradeError::Selection(error) => Some(error),
+            _ => None,
         }
     }
 }
 
+#[derive(Debug)]
 pub enum BuyError {
-    SameValue,
-    NotEnoughPoints,
+    Selection(SelectionError),
+    /// A selected card that is worth at least the price.
+    SameValue { card: usize, price: usize },
+    NotEnoughPoints { price: usize, offered: usize },
     OutOfCards(Value),
+    OutOfMonads,
 }
 
 impl fmt::Display for BuyError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         use self::BuyError::*;
         match self {
-            SameValue => write!(fmt, "Can't buy something of the same value!"),
-            NotEnoughPoints => write!(fmt, "Not enough points!"),
-            OutOfCards(value) => write!(fmt, "The {} deck is out of cards!", value),
+            Selection(error)                   => write!(fmt, "{}", error),
+            SameValue { card, price }          => {
+                write!(fmt, "Can't buy something of the same value! Card {} is worth {} or more", card, price)
+            },
+            NotEnoughPoints { price, offered } => write!(fmt, "Not enough points! {} offered for {}", offered, price),
+            OutOfCards(value)                  => write!(fmt, "The {:?} deck is out of cards!", value),
+            OutOfMonads                        => write!(fmt, "There are no monads left!"),
+        }
+    }
+}
+
+impl error::Error for BuyError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            BuyError::Selection(error) => Some(error),
+            _ => None,
         }
     }
 }
 
+#[derive(Clone, PartialEq)]
 pub struct Game {
     pub players: Vec<Player>,
     pub table: Table,
+    /// The player whose turn it is.
+    pub turn: usize,
+    /// Whether the current player has traded, bought or leapt this turn.
+    pub acted: bool,
+    /// How many turns have been finished, counting every player's.
+    pub turns_played: usize,
+    pub rules: Rules,
 }
 
 impl Game {
     pub fn new(num_players: NumPlayers) -> Self {
-        let mut table = Table::new(num_players);
-        let mut players = Game::generate_players(num_players);
+        Game::with_rng(num_players, &mut thread_rng())
+    }
 
-        for player in &mut players {
-            player.hand.extend(table.common.drain(0..6));
-        }
+    /// Deals a game using `rng` for every shuffle, so a seeded rng always deals the same game.
+    /// Identities are balanced between warm and cold colours.
+    pub fn with_rng(num_players: NumPlayers, rng: &mut impl Rng) -> Self {
+        Game::with_policy(num_players, Identities::default(), &card::STANDARD, &card::SIX_COLORS, rng)
+            .expect("Only a draft needs the players to choose")
+    }
 
-        Game { players, table }
+    /// Deals a game on `ladder` in `colors`, with identities handed out by `policy`, or `None` for
+    /// a draft.
+    pub fn with_policy(
+        num_players: NumPlayers,
+        policy: Identities,
+        ladder: &'static Ladder,
+        colors: &'static ColorSet,
+        rng: &mut impl Rng,
+    ) -> Option<Self> {
+        let identities = policy.assign(num_players, colors, rng)?;
+        let game = Game::with_identities(&identities, ladder, colors, rng);
+        Some(game.expect("Policies hand out different colours from the set"))
     }
 
     pub fn flip(&mut self) -> Result<(), FlipError> {
         if self.table.discard.is_empty() { return Err(FlipError::EmptyDiscardPile); }
-        if !self.table.deck(card::Value::Common).is_empty() { return Err(FlipError::NonEmptyCommonDeck); }
+        let commons = self.table.deck(card::Value::Common).len();
+        if commons > 0 { return Err(FlipError::NonEmptyCommonDeck(commons)); }
 
+        if let Some(seed) = self.rules.shuffle_seed {
+            let seed = seed.wrapping_add(self.turns_played as u64);
+            self.table.discard.shuffle(&mut StdRng::seed_from_u64(seed));
+        }
         self.table.common.append(&mut self.table.discard);
 
         Ok(())
     }
 
-    pub fn draw(&mut self, player: usize) -> Result<(), ()> {
+    pub fn draw(&mut self, player: usize) -> Result<(), DrawError> {
+        if self.rules.auto_flip && self.table.common.is_empty() {
+            // With nothing to flip either, the draw below fails.
+            drop(self.flip());
+        }
+
         match self.table.draw_top(card::Value::Common) {
             Some(card) => {
                 self.players[player].hand.push(card);
                 Ok(())
             },
-            None => Err(()),
+            None => Err(DrawError { discarded: self.table.discard.len() }),
         }
     }
 
-    pub fn leap(&mut self, player: usize, cards: &mut Vec<usize>) -> Result<(), LeapError> {
+    pub fn leap(&mut self, player: usize, cards: &mut [usize]) -> Result<(), LeapError> {
         let player = &mut self.players[player];
+        player.check_selection(cards).map_err(LeapError::Selection)?;
 
-        if cards.len() < 4 || cards.len() > 6 {
-            return Err(LeapError::NumOfCards(cards.len()));
-        }
+        let value = match self.table.ladder.leap(cards.len()) {
+            Some(value) => value,
+            None => return Err(LeapError::NumOfCards(cards.len())),
+        };
 
-        if ! cards.iter().all(|&card| player.hand[card].value.is_common()) {
-            return Err(LeapError::NotAllCommons);
+        if let Some(&card) = cards.iter().find(|&&card| !player.hand[card].value.is_common()) {
+            return Err(LeapError::NotAllCommons(card));
         }
 
-        let card = self.table.draw_top(
-            Game::translate_commons_for_leap(cards.len())
-        ).unwrap();
+        let card = match self.table.draw_top(value) {
+            Some(card) => card,
+            None => return Err(LeapError::OutOfCards(value)),
+        };
 
         cards.sort();
         for elt in cards.iter().rev() {
@@ -172,10 +349,12 @@ impl Game {
     pub fn buy(
         &mut self,
         player: usize,
-        cards: &mut Vec<usize>,
+        cards: &mut [usize],
         deck_or_monad: ValueOrMonad
-    ) -> Result<bool, BuyError> {
+    ) -> Result<(usize, bool), BuyError> {
         let player = &mut self.players[player];
+        player.check_selection(cards).map_err(BuyError::Selection)?;
+
         let nums = cards
             .iter()
             .map(|p| &player.hand[*p])
@@ -183,37 +362,54 @@ impl Game {
             .collect::<Vec<_>>();
 
         let buy_value = nums.iter().sum::<usize>();
-        let max_value = nums.into_iter().max().unwrap_or(0);
-
-        let cost = deck_or_monad.points();
+        let cost = self.table.ladder.price(deck_or_monad);
 
-        if max_value >= cost {
-            return Err(BuyError::SameValue);
+        if let Some(i) = nums.iter().position(|&num| num >= cost) {
+            return Err(BuyError::SameValue { card: cards[i], price: cost });
         }
         if buy_value < cost {
-            return Err(BuyError::NotEnoughPoints);
+            return Err(BuyError::NotEnoughPoints { price: cost, offered: buy_value });
         }
 
-        let drew_card;
-        match deck_or_monad {
+        let drew_card = match deck_or_monad {
             ValueOrMonad::Value(value) => {
                 if player.draw_card(value, &mut self.table).is_none() {
                     return Err(BuyError::OutOfCards(value));
                 }
-                drew_card = true;
+                true
             },
             ValueOrMonad::Monad => {
-                player.draw_monad(&mut self.table);
-                drew_card = false;
+                if player.draw_monad(&mut self.table).is_none() {
+                    return Err(BuyError::OutOfMonads);
+                }
+                false
             },
-        }
+        };
+
+        let overpaid = buy_value - cost;
+        let refund = cards
+            .iter()
+            .map(|&i| &player.hand[i])
+            .filter_map(|card| card.special.map(|special| special.ability.refund(card, overpaid)))
+            .sum::<usize>()
+            .min(overpaid);
 
         cards.sort();
         for i in cards.iter().rev() {
             self.table.return_card(player.hand.remove(*i));
         }
 
-        Ok(drew_card)
+        // Change comes from the common deck, for as long as it lasts.
+        let common = self.table.ladder.common();
+        let mut change = 0;
+        for _ in 0..refund / common.points() {
+            if player.draw_card(common, &mut self.table).is_none() {
+                break;
+            }
+            change += 1;
+        }
+
+        Ok((drew_card as usize + change, !drew_card))
     }
 
     pub fn trade(
@@ -227,15 +423,22 @@ impl Game {
         let mut drew_monad = false;
 
         let player = &mut self.players[player];
+        player.check_selection(&[card1, card2]).map_err(TradeError::Selection)?;
         let value = player.trade_value(card1, card2)?;
 
+        if bonus && !player.can_take_bonus(card1, card2) {
+            return Err(TradeError::NoBonus);
+        }
+
         if let Some(succ_value) = value.succ() {
             if player.draw_card(succ_value, &mut self.table).is_none() {
                 return Err(TradeError::OutOfCards(succ_value));
             }
             num_cards += 1;
         } else {
-            player.draw_monad(&mut self.table).unwrap();
+            if player.draw_monad(&mut self.table).is_none() {
+                return Err(TradeError::OutOfMonads);
+            }
             drew_monad = true;
         }
 
@@ -261,7 +464,6 @@ impl Game {
     }
 
     pub fn check_player_end(&self, player: usize) -> bool {
-        use self::card::Value::*;
         let player = &self.players[player];
         if ! (self.table.common.is_empty() || self.table.discard.is_empty() )  {
             return false;
@@ -289,9 +491,9 @@ impl Game {
 
         //-------------------------------------------------------------------
 
-        let mut sorted_hand = player.hand.clone();
+        let mut sorted_hand = player.hand.to_vec();
         let mut sum = 0;
-        let mut highest_value = Common;
+        let mut highest_value = self.table.ladder.common();
         let mut num_commons = 0;
 
         sorted_hand.sort_by_key(|card| card.value);
@@ -313,7 +515,7 @@ impl Game {
                 }
                 value_iter = value;
             }
-            if sum >= 80 {
+            if sum >= self.table.ladder.monad_price {
                 return false;
             }
         }
@@ -321,52 +523,28 @@ impl Game {
 
         //-------------------------------------------------------------------
 
-        if num_commons > 5 && ! self.table.deck(Quint).is_empty() {
-            return false;
-        }
-        if num_commons > 4 && ! self.table.deck(Quad).is_empty() {
-            return false;
-        }
-        if num_commons > 3 && ! self.table.deck(Tri).is_empty() {
-            return false;
+        let leaps = self.table.ladder.leaps();
+        for count in *leaps.start()..=num_commons.min(*leaps.end()) {
+            if let Some(value) = self.table.ladder.leap(count) {
+                if ! self.table.deck(value).is_empty() {
+                    return false;
+                }
+            }
         }
 
         true
     }
 
-    pub fn player_took_bonus(&mut self, player: usize) -> &mut bool {
-        &mut self.players[player].took_bonus
+    pub fn is_over(&self) -> bool {
+        self.end_reason().is_some()
     }
 
-    fn generate_players(num_players: NumPlayers) -> Vec<Player> {
-        let mut colors = card::COLORS.to_vec();
-
-        match num_players {
-            NumPlayers::Two => {
-                colors.shuffle(&mut thread_rng());
-                colors.drain(0..4);
-            },
-            NumPlayers::Three => {
-                colors.drain(0..3);
-            },
-            NumPlayers::Four => {
-                colors.remove(5);
-                colors.remove(2);
-            },
-        }
-
-        colors.shuffle(&mut thread_rng());
-
-        colors.into_iter().map(Player::from).collect()
-    }
-
-    // Using option for this makes it ugly and checks ensure that values range from 4-6
-    pub fn translate_commons_for_leap(value: usize) -> self::card::Value {
-        use self::card::Value::*;
-        match value {
-            4 => Tri,
-            5 => Quad,
-            _ => Quint,
-        }
+    /// The players whose side holds the most monads. Several players can share the win, as
+    /// partners always do.
+    pub fn winners(&self) -> Vec<usize> {
+        let most = (0..self.players.len()).map(|i| self.score(i)).max().unwrap_or(0);
+        (0..self.players.len())
+            .filter(|&i| self.score(i) == most)
+            .collect()
     }
 }
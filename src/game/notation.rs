@@ -0,0 +1,324 @@
+use itertools::Itertools;
+use std::{error, fmt, str::FromStr};
+
+use super::{
+    action::{Action, Outcome},
+    card::{self, Card, Color, Value, ValueOrMonad},
+    expansion::Special,
+    Game,
+};
+
+/// A move as it happened, naming cards instead of hand positions so it still reads correctly
+/// once the hand has been rearranged.
+///
+/// ```text
+/// D                                  draw
+/// F                                  flip
+/// E                                  end the turn
+/// T Bi.Or+Bi.Bl* -> Tri.Gr,Cm.Rd     trade two cards, * for a bonus, and what was drawn
+/// B Cm.Rd Bi.Pu Tri.Ye => Quad       buy from a deck, or => Monad
+/// L4 Cm.Rd Cm.Bl Cm.Ye Cm.Gr -> Tri  leap with four commons
+/// P Bi.Gr                            pass a card to your partner
+/// B Cm.Rd Cm.Rd/3 => Bi              the first and third red commons in the hand
+/// B Tri.Bl~Rf Quad.Rd => Quint       an expansion card's abbreviation follows a `~`
+/// ```
+///
+/// Cards are taken from the left of the hand unless a `/n` says which of several identical cards
+/// was played, since that decides the order they are discarded in.
+///
+/// What a trade drew is optional when reading a move, as are the commons of a leap, in which case
+/// the leftmost commons in the hand are used.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Move {
+    Draw,
+    Flip,
+    End,
+    Trade { cards: [Chosen; 2], bonus: bool, received: Vec<Card>, monad: bool },
+    Buy { cards: Vec<Chosen>, target: ValueOrMonad },
+    Leap { count: usize, cards: Vec<Chosen>, target: Value },
+    Pass { card: Chosen },
+}
+
+/// A card picked from the hand, and which copy of it when that isn't the leftmost one left.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Chosen {
+    pub card: Card,
+    /// Counting identical cards in the hand from 1.
+    pub copy: Option<usize>,
+}
+
+#[derive(Debug)]
+pub enum NotationError {
+    Syntax(String),
+    BadCard(String),
+    BadTarget(String),
+    NotInHand(Card),
+    NoSuchCopy(Card, usize),
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::NotationError::*;
+        match self {
+            Syntax(source) => write!(fmt, "Not a valid move: {}", source),
+            BadCard(source) => write!(fmt, "Not a valid card: {}", source),
+            BadTarget(source) => write!(fmt, "Not a valid deck: {}", source),
+            NotInHand(card) => write!(fmt, "{} is not in the hand", card),
+            NoSuchCopy(card, copy) => write!(fmt, "There is no {} number {} in the hand", card, copy),
+        }
+    }
+}
+
+impl error::Error for NotationError {}
+
+struct Short<'a>(&'a Card);
+
+impl<'a> fmt::Display for Short<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}.{}", self.0.value.abbreviation(), self.0.color.abbreviation())?;
+        match self.0.special {
+            Some(special) => write!(fmt, "~{}", special.abbreviation),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Chosen {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", Short(&self.card))?;
+        match self.copy {
+            Some(copy) => write!(fmt, "/{}", copy),
+            None => Ok(()),
+        }
+    }
+}
+
+// Records don't say which ladder they were played on, but every ladder abbreviates its values
+// the same way.
+fn parse_value(source: &str) -> Option<Value> {
+    card::LADDERS.iter().flat_map(|ladder| ladder.values()).find(|value| value.abbreviation() == source)
+}
+
+// Likewise, a colour is abbreviated the same way in every set that has it.
+fn parse_color(source: &str) -> Option<Color> {
+    card::COLOR_SETS.iter().flat_map(|set| set.colors()).find(|color| color.abbreviation() == source)
+}
+
+fn parse_card(source: &str) -> Result<Card, NotationError> {
+    let mut parts = source.splitn(2, '~');
+    let mut plain = parts.next().unwrap_or(source).splitn(2, '.');
+    let value = plain.next().and_then(parse_value);
+    let color = plain.next().and_then(parse_color);
+    let special = parts.next().map(Special::abbreviated);
+    match (value, color, special) {
+        (Some(value), Some(color), None) => Ok(Card::new(value, color)),
+        (Some(value), Some(color), Some(Some(special))) => Ok(Card { value, color, special: Some(special) }),
+        _ => Err(NotationError::BadCard(source.to_string())),
+    }
+}
+
+fn parse_chosen(source: &str) -> Result<Chosen, NotationError> {
+    let mut parts = source.splitn(2, '/');
+    let card = parse_card(parts.next().unwrap_or(source))?;
+    let copy = match parts.next() {
+        Some(copy) => Some(
+            copy.parse().ok().filter(|&copy| copy > 0).ok_or_else(|| NotationError::BadCard(source.to_string()))?
+        ),
+        None => None,
+    };
+    Ok(Chosen { card, copy })
+}
+
+// Tiers may be written with or without the colour of the card that was drawn.
+fn parse_tier(source: &str) -> Result<Value, NotationError> {
+    parse_value(source.split('.').next().unwrap_or(source))
+        .ok_or_else(|| NotationError::BadTarget(source.to_string()))
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Move::Draw => write!(fmt, "D"),
+            Move::Flip => write!(fmt, "F"),
+            Move::End => write!(fmt, "E"),
+            Move::Trade { cards, bonus, received, monad } => {
+                write!(fmt, "T {}+{}{}", cards[0], cards[1], if *bonus { "*" } else { "" })?;
+                let received = (if *monad { Some("Monad".to_string()) } else { None })
+                    .into_iter()
+                    .chain(received.iter().map(|card| Short(card).to_string()));
+                write!(fmt, " -> {}", received.format(","))
+            },
+            Move::Buy { cards, target } => {
+                write!(fmt, "B {} => ", cards.iter().format(" "))?;
+                match target {
+                    ValueOrMonad::Value(value) => write!(fmt, "{}", value.abbreviation()),
+                    ValueOrMonad::Monad => write!(fmt, "Monad"),
+                }
+            },
+            Move::Leap { count, cards, target } => {
+                write!(fmt, "L{}", count)?;
+                for card in cards {
+                    write!(fmt, " {}", card)?;
+                }
+                write!(fmt, " -> {}", target.abbreviation())
+            },
+            Move::Pass { card } => write!(fmt, "P {}", card),
+        }
+    }
+}
+
+impl FromStr for Move {
+    type Err = NotationError;
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let syntax = || NotationError::Syntax(source.to_string());
+        let source = source.trim();
+
+        match source {
+            "D" => return Ok(Move::Draw),
+            "F" => return Ok(Move::Flip),
+            "E" => return Ok(Move::End),
+            _ => {},
+        }
+
+        if let Some(rest) = source.strip_prefix("T ") {
+            let mut sides = rest.splitn(2, "->");
+            let given = sides.next().ok_or_else(syntax)?.trim();
+            let bonus = given.ends_with('*');
+            let given: Vec<&str> = given.trim_end_matches('*').split('+').collect();
+            let cards = match given.as_slice() {
+                [card1, card2] => [parse_chosen(card1)?, parse_chosen(card2)?],
+                _ => return Err(syntax()),
+            };
+
+            let mut received = Vec::new();
+            let mut monad = false;
+            for card in sides.next().unwrap_or("").split(',').map(str::trim).filter(|card| !card.is_empty()) {
+                if card == "Monad" {
+                    monad = true;
+                } else {
+                    received.push(parse_card(card)?);
+                }
+            }
+
+            return Ok(Move::Trade { cards, bonus, received, monad });
+        }
+
+        if let Some(rest) = source.strip_prefix("B ") {
+            let mut sides = rest.splitn(2, "=>");
+            let cards = sides
+                .next()
+                .ok_or_else(syntax)?
+                .split_whitespace()
+                .map(parse_chosen)
+                .collect::<Result<Vec<_>, _>>()?;
+            let target = match sides.next().ok_or_else(syntax)?.trim() {
+                "Monad" => ValueOrMonad::Monad,
+                tier => ValueOrMonad::Value(parse_tier(tier)?),
+            };
+
+            return Ok(Move::Buy { cards, target });
+        }
+
+        if let Some(rest) = source.strip_prefix('L') {
+            let mut sides = rest.splitn(2, "->");
+            let mut given = sides.next().ok_or_else(syntax)?.split_whitespace();
+            let count = given.next().and_then(|count| count.parse().ok()).ok_or_else(syntax)?;
+            let cards = given.map(parse_chosen).collect::<Result<Vec<_>, _>>()?;
+            let target = parse_tier(sides.next().ok_or_else(syntax)?.trim())?;
+
+            return Ok(Move::Leap { count, cards, target });
+        }
+
+        if let Some(card) = source.strip_prefix("P ") {
+            return Ok(Move::Pass { card: parse_chosen(card.trim())? });
+        }
+
+        Err(syntax())
+    }
+}
+
+/// Finds a different card in `hand` for each of `cards`.
+fn find_cards(hand: &[Card], cards: &[Chosen]) -> Result<Vec<usize>, NotationError> {
+    let mut indexes: Vec<usize> = Vec::new();
+    for Chosen { card, copy } in cards {
+        let mut copies = (0..hand.len()).filter(|&i| hand[i] == *card);
+        let index = match copy {
+            Some(copy) => copies
+                .nth(copy - 1)
+                .filter(|i| !indexes.contains(i))
+                .ok_or_else(|| NotationError::NoSuchCopy(card.clone(), *copy))?,
+            None => copies
+                .find(|i| !indexes.contains(i))
+                .ok_or_else(|| NotationError::NotInHand(card.clone()))?,
+        };
+        indexes.push(index);
+    }
+    Ok(indexes)
+}
+
+/// Names the cards at `indexes`, marking the copy wherever `find_cards` would pick another.
+fn choose_cards(hand: &[Card], indexes: &[usize]) -> Vec<Chosen> {
+    let mut chosen: Vec<Chosen> = Vec::new();
+    for (n, &index) in indexes.iter().enumerate() {
+        let card = hand[index].clone();
+        let leftmost = (0..hand.len()).find(|i| hand[*i] == card && !indexes[..n].contains(i));
+        let copy = if leftmost == Some(index) {
+            None
+        } else {
+            Some((0..=index).filter(|&i| hand[i] == card).count())
+        };
+        chosen.push(Chosen { card, copy });
+    }
+    chosen
+}
+
+impl Move {
+    /// Describes `action`, which `player` just took in `before` with `outcome`, leaving `after`.
+    pub fn record(before: &Game, after: &Game, player: usize, action: &Action, outcome: &Outcome) -> Move {
+        let hand = &before.players[player].hand;
+
+        match action {
+            Action::Draw => Move::Draw,
+            Action::Flip => Move::Flip,
+            Action::End => Move::End,
+            Action::Trade { card1, card2, bonus } => {
+                let new_hand = &after.players[player].hand;
+                let mut cards = choose_cards(hand, &[*card1, *card2]).into_iter();
+                Move::Trade {
+                    cards: [cards.next().unwrap(), cards.next().unwrap()],
+                    bonus: *bonus,
+                    received: new_hand[new_hand.len() - outcome.cards..].to_vec(),
+                    monad: outcome.monads > 0,
+                }
+            },
+            Action::Buy { cards: indexes, target } => Move::Buy { cards: choose_cards(hand, indexes), target: *target },
+            Action::Leap { cards: indexes } => Move::Leap {
+                count: indexes.len(),
+                cards: choose_cards(hand, indexes),
+                target: before.table.ladder.leap(indexes.len()).expect("A leap that happened has a target"),
+            },
+            Action::Pass { card } => Move::Pass { card: choose_cards(hand, &[*card]).remove(0) },
+        }
+    }
+
+    /// The action that plays this move for the current player of `game`.
+    pub fn to_action(&self, game: &Game) -> Result<Action, NotationError> {
+        let hand = &game.players[game.turn].hand;
+
+        Ok(match self {
+            Move::Draw => Action::Draw,
+            Move::Flip => Action::Flip,
+            Move::End => Action::End,
+            Move::Trade { cards, bonus, .. } => {
+                let indexes = find_cards(hand, cards)?;
+                Action::Trade { card1: indexes[0], card2: indexes[1], bonus: *bonus }
+            },
+            Move::Buy { cards, target } => Action::Buy { cards: find_cards(hand, cards)?, target: *target },
+            Move::Leap { count, cards, .. } if cards.is_empty() => Action::Leap {
+                cards: hand.find_all(Card::is_common).into_iter().take(*count).collect(),
+            },
+            Move::Leap { cards, .. } => Action::Leap { cards: find_cards(hand, cards)? },
+            Move::Pass { card } => Action::Pass { card: find_cards(hand, std::slice::from_ref(card))?[0] },
+        })
+    }
+}
@@ -0,0 +1,144 @@
+use std::{cmp::Reverse, str::FromStr};
+
+use super::{
+    card::{Card, ValueOrMonad},
+    player::Player,
+    BuyError,
+    Game,
+    ParseError,
+};
+
+#[derive(Clone, Copy, Default)]
+pub struct PaymentPreference {
+    pub keep_wilds: bool,
+    pub keep_bonus_pairs: bool,
+}
+
+impl PaymentPreference {
+    pub fn keep_all() -> Self {
+        PaymentPreference { keep_wilds: true, keep_bonus_pairs: true }
+    }
+
+    fn penalty(self, player: &Player, card: usize) -> usize {
+        let mut penalty = 0;
+        if self.keep_wilds && player.is_player_wild(&player.hand[card]) {
+            penalty += 1;
+        }
+        if self.keep_bonus_pairs && player.in_bonus_pair(card) {
+            penalty += 1;
+        }
+        penalty
+    }
+}
+
+/// `keep` holds on to wilds and bonus pairs where the price allows, and `spend` pays with whatever
+/// overpays least.
+impl FromStr for PaymentPreference {
+    type Err = ParseError;
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            "keep"  => Ok(PaymentPreference::keep_all()),
+            "spend" => Ok(PaymentPreference::default()),
+            _       => Err(ParseError::new("payment preference (keep or spend)", source)),
+        }
+    }
+}
+
+// Candidates are ranked by overpayment first, then by how many preferred cards they spend,
+// then by how many cards they spend at all.
+type Rank = (usize, usize, usize);
+
+struct Search<'a> {
+    cost: usize,
+    // (hand index, points, penalty), sorted by descending points
+    candidates: Vec<(usize, usize, usize)>,
+    // remaining[i] is the sum of the points of candidates[i..]
+    remaining: Vec<usize>,
+    selected: Vec<usize>,
+    best: Option<(Rank, Vec<usize>)>,
+    player: &'a Player,
+}
+
+impl<'a> Search<'a> {
+    fn new(player: &'a Player, cost: usize, preference: PaymentPreference) -> Self {
+        let mut candidates: Vec<_> = (0..player.hand.len())
+            .map(|i| (i, player.hand[i].num(), preference.penalty(player, i)))
+            .filter(|&(_, points, _)| points < cost)
+            .collect();
+        candidates.sort_by_key(|&(i, points, _)| (Reverse(points), player.hand[i].color.index()));
+
+        let mut remaining = vec![0; candidates.len() + 1];
+        for i in (0..candidates.len()).rev() {
+            remaining[i] = remaining[i + 1] + candidates[i].1;
+        }
+
+        Search { cost, candidates, remaining, selected: Vec::new(), best: None, player }
+    }
+
+    fn run(&mut self, next: usize, sum: usize, penalty: usize) {
+        if sum >= self.cost {
+            let rank = (sum - self.cost, penalty, self.selected.len());
+            if self.best.as_ref().is_none_or(|(best, _)| rank < *best) {
+                self.best = Some((rank, self.selected.clone()));
+            }
+            return;
+        }
+
+        if next == self.candidates.len() || sum + self.remaining[next] < self.cost {
+            return;
+        }
+
+        let (index, points, card_penalty) = self.candidates[next];
+        self.selected.push(index);
+        self.run(next + 1, sum + points, penalty + card_penalty);
+        self.selected.pop();
+
+        // Skipping a card only makes sense if the next one is actually different,
+        // otherwise we'd explore the same payment twice.
+        let mut skip = next + 1;
+        while skip < self.candidates.len() && self.is_equivalent(next, skip) {
+            skip += 1;
+        }
+        self.run(skip, sum, penalty);
+    }
+
+    fn is_equivalent(&self, a: usize, b: usize) -> bool {
+        let a = &self.player.hand[self.candidates[a].0];
+        let b = &self.player.hand[self.candidates[b].0];
+        a.value == b.value && a.color == b.color
+    }
+}
+
+impl Game {
+    /// Finds the cards in `player`'s hand that pay for `target` with the least overpayment.
+    ///
+    /// Cards worth as much as the target are never used, since `buy` would reject them.
+    /// Returned indexes are sorted in ascending order.
+    pub fn optimal_payment(
+        &self,
+        player: usize,
+        target: ValueOrMonad,
+        preference: PaymentPreference,
+    ) -> Result<Vec<usize>, BuyError> {
+        if let ValueOrMonad::Value(value) = target {
+            if self.table.deck(value).is_empty() {
+                return Err(BuyError::OutOfCards(value));
+            }
+        }
+
+        let mut search = Search::new(&self.players[player], self.table.ladder.price(target), preference);
+        search.run(0, 0, 0);
+
+        match search.best {
+            Some((_, mut cards)) => {
+                cards.sort();
+                Ok(cards)
+            },
+            None => {
+                let price = self.table.ladder.price(target);
+                let offered = self.players[player].hand.iter().map(Card::num).filter(|&num| num < price).sum();
+                Err(BuyError::NotEnoughPoints { price, offered })
+            },
+        }
+    }
+}
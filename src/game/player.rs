@@ -1,13 +1,17 @@
 use super::{
     card::{self, Monad, Deck, Card, Color, Value},
     table::Table,
+    SelectionError,
     TradeError,
 };
 
+#[derive(Clone, PartialEq)]
 pub struct Player {
     pub hand: Deck,
     pub identity: Color,
     pub took_bonus: bool,
+    /// How many cards they have passed to their partner this turn.
+    pub passed: usize,
     pub monads: Vec<Monad>,
 }
 
@@ -17,18 +21,33 @@ impl From<card::Color> for Player {
             hand: Deck::default(),
             identity: color,
             took_bonus: false,
+            passed: 0,
             monads: Vec::new(),
         }
     }
 }
 
 impl Player {
-    pub fn trade_value(&self, card1: usize, card2: usize) -> Result<Value, TradeError> {
-        let card1 = &self.hand[card1];
-        let card2 = &self.hand[card2];
+    /// Makes sure every index points at a different card in the hand.
+    pub fn check_selection(&self, cards: &[usize]) -> Result<(), SelectionError> {
+        for (i, &card) in cards.iter().enumerate() {
+            if card >= self.hand.len() {
+                return Err(SelectionError::NoSuchCard(card));
+            }
+            if cards[..i].contains(&card) {
+                return Err(SelectionError::DuplicateCard(card));
+            }
+        }
+
+        Ok(())
+    }
 
-        if card1.temp() == card2.temp() {
-            return Err(TradeError::SameTemperature);
+    pub fn trade_value(&self, first: usize, other: usize) -> Result<Value, TradeError> {
+        let card1 = &self.hand[first];
+        let card2 = &self.hand[other];
+
+        if card1.trade_color(card2).temp() == card2.trade_color(card1).temp() {
+            return Err(TradeError::SameTemperature(first, other));
         }
 
         if card1.value == card2.value {
@@ -43,22 +62,27 @@ impl Player {
             return Ok(card1.value);
         }
 
-        Err(TradeError::NotSameValueOrIdentity)
+        Err(TradeError::NotSameValueOrIdentity(card1.value, card2.value))
     }
 
-    pub fn can_take_bonus(&self, card1: usize, card2: usize) -> bool {
-        use self::Color::*;
-        let bonus_match = match (self.hand[card1].color, self.hand[card2].color) {
-            (Orange, Blue  ) |
-            (Blue,   Orange) |
-            (Red,    Purple) |
-            (Purple, Red   ) |
-            (Yellow, Green ) |
-            (Green,  Yellow) => true,
-            _                => false,
-        };
+    /// Whether two cards in the hand trade for a bonus, once per turn, with a Prism taking whichever
+    /// colour completes the pair.
+    pub fn is_bonus_pair(&self, card1: usize, card2: usize) -> bool {
+        let (card1, card2) = (&self.hand[card1], &self.hand[card2]);
+        let bonus_match = card1.trade_color(card2).bonus_partner() == card2.trade_color(card1);
         // Not only do the colors need to match a bonus pair, but you can't use a wild to get a bonus.
-        bonus_match && self.hand[card1].value == self.hand[card2].value && ! self.took_bonus
+        bonus_match && card1.value == card2.value
+    }
+
+    pub fn can_take_bonus(&self, card1: usize, card2: usize) -> bool {
+        self.is_bonus_pair(card1, card2) && ! self.took_bonus
+    }
+
+    /// Whether `card` makes a bonus pair with another card in the hand, even if the bonus for this
+    /// turn has already been taken.
+    pub fn in_bonus_pair(&self, card: usize) -> bool {
+        !self.hand[card].is_common()
+            && (0..self.hand.len()).any(|other| other != card && self.is_bonus_pair(card, other))
     }
 
     pub fn draw_card(&mut self, value: Value, table: &mut Table) -> Option<&Card> {
@@ -70,7 +94,7 @@ impl Player {
         }
     }
 
-    pub fn indexes_to_cards(&self, cards: &Vec<usize>) -> Vec<Card> {
+    pub fn indexes_to_cards(&self, cards: &[usize]) -> Vec<Card> {
         cards.iter().map(|x| self.hand[*x].clone()).collect::<Vec<_>>()
     }
 
@@ -78,7 +102,7 @@ impl Player {
         table.monad.pop().map(|monad| self.monads.push(monad))
     }
 
-    fn is_player_wild(&self, card: &Card) -> bool {
+    pub fn is_player_wild(&self, card: &Card) -> bool {
         self.identity == card.color && ! card.is_common()
     }
 }
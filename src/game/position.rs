@@ -0,0 +1,260 @@
+use itertools::Itertools;
+use std::{error, fmt};
+
+use super::{
+    builder::{BuildError, GameBuilder},
+    card::{self, Card, Color, ColorSet, Deck, Ladder, Value},
+    expansion::Special,
+    rules::Rules,
+    Game,
+    NumPlayers,
+    ParseError,
+};
+
+/// Problems reading a position written by `Game::position`.
+#[derive(Debug)]
+pub enum PositionError {
+    MissingField(&'static str),
+    ExtraField(String),
+    BadPlayer(String),
+    BadCard(String),
+    BadColor(String),
+    BadNumber(String),
+    BadTiers(String),
+    BadColorSet(String),
+    BadSpecial(String),
+    BadRules(ParseError),
+    PlayerCount(usize),
+    Build(BuildError),
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::PositionError::*;
+        match self {
+            MissingField(field) => write!(fmt, "The position is missing the {} field", field),
+            ExtraField(field) => write!(fmt, "Unexpected field at the end of the position: {}", field),
+            BadPlayer(player) => write!(fmt, "Not a valid player: {}", player),
+            BadCard(card) => write!(fmt, "Not a valid card: {}", card),
+            BadColor(color) => write!(fmt, "Not a valid color: {}", color),
+            BadNumber(number) => write!(fmt, "Not a valid number: {}", number),
+            BadColorSet(name) => write!(fmt, "Not a colour set: {}", name),
+            BadSpecial(name) => write!(fmt, "Not an expansion card: {}", name),
+            BadRules(error) => write!(fmt, "Unknown rules: {}", error),
+            BadTiers(tiers) => write!(fmt, "No ladder has this many tier decks: {}", tiers),
+            PlayerCount(count) => write!(fmt, "Games have 2 to 4 players, not {}", count),
+            Build(error) => write!(fmt, "{}", error),
+        }
+    }
+}
+
+impl error::Error for PositionError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            PositionError::Build(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+// An expansion card's name follows its colour after a `~`.
+fn color_and_special(card: &Card) -> String {
+    match card.special {
+        Some(special) => format!("{}~{}", card.color, special.name),
+        None => card.color.to_string(),
+    }
+}
+
+fn write_colors(fmt: &mut fmt::Formatter, deck: &Deck) -> fmt::Result {
+    if deck.is_empty() {
+        write!(fmt, "-")
+    } else {
+        write!(fmt, "{}", deck.iter().map(color_and_special).join(","))
+    }
+}
+
+fn parse_number(source: &str) -> Result<usize, PositionError> {
+    source.parse().map_err(|_| PositionError::BadNumber(source.to_string()))
+}
+
+fn parse_color(source: &str, colors: &'static ColorSet) -> Result<Color, PositionError> {
+    colors.color(source).ok_or_else(|| PositionError::BadColor(source.to_string()))
+}
+
+fn parse_color_and_special(source: &str, value: Value, colors: &'static ColorSet) -> Result<Card, PositionError> {
+    let mut parts = source.splitn(2, '~');
+    let color = parse_color(parts.next().unwrap_or(source), colors)?;
+    let special = match parts.next() {
+        Some(name) => Some(Special::named(name).ok_or_else(|| PositionError::BadSpecial(name.to_string()))?),
+        None => None,
+    };
+    Ok(Card { value, color, special })
+}
+
+fn parse_card(source: &str, ladder: &'static Ladder, colors: &'static ColorSet) -> Result<Card, PositionError> {
+    let mut parts = source.splitn(2, '.');
+    match (parts.next().and_then(|value| ladder.value(value)), parts.next()) {
+        (Some(value), Some(color)) => parse_color_and_special(color, value, colors),
+        _ => Err(PositionError::BadCard(source.to_string())),
+    }
+}
+
+fn parse_colors(source: &str, value: Value, colors: &'static ColorSet) -> Result<Vec<Card>, PositionError> {
+    if source == "-" {
+        return Ok(Vec::new());
+    }
+    source
+        .split(',')
+        .map(|color| parse_color_and_special(color, value, colors))
+        .collect()
+}
+
+/// Writes the whole game on one line, as six space separated fields, then the optional turns played
+/// and colour set:
+///
+/// ```text
+/// <players> <turn> <common> <discard> <Bi>|<Tri>|<Quad>|<Quint> <monad pile> [turns played] [colours]
+/// ```
+///
+/// Players are separated by `/` and written as `Identity:monads:hand`, with a `*` after the
+/// identity if they took a bonus this turn, then a `>` for each card passed to their partner this
+/// turn, and the hand as comma separated `Value.Color` cards. The turn is the number of the
+/// current player, followed by `+` once they have acted. Decks are comma separated colours from the
+/// bottom up, or `-` if empty, and the tier decks above Common are joined by `|`, so their number
+/// also says which ladder the game is on. An expansion card has `~Name` after its colour, in hands
+/// and decks alike. The number of turns played so far is only written once there is one, or the
+/// name of the colour set follows it, which is only written when it isn't the standard one. Rules
+/// other than the standard ones come last, after a `;`, written as in a record's `Rules` header,
+/// such as `; Standard, turn limit 40, teams 1`. For example, two players part way through a game:
+///
+/// ```text
+/// Blue:0:Common.Red,Bi.Green/Red*:1:Common.Blue 2+ Red,Purple - Orange,Blue|-|Red|Green,Yellow 11 37
+/// ```
+pub struct Position<'a>(pub &'a Game);
+
+impl<'a> fmt::Display for Position<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let game = self.0;
+        for (i, player) in game.players.iter().enumerate() {
+            if i > 0 {
+                write!(fmt, "/")?;
+            }
+            write!(
+                fmt,
+                "{}{}{}:{}:{}",
+                player.identity,
+                if player.took_bonus { "*" } else { "" },
+                ">".repeat(player.passed),
+                player.monads.len(),
+                player.hand.iter().map(|card| format!("{:?}.{}", card.value, color_and_special(card))).join(","),
+            )?;
+        }
+
+        write!(fmt, " {}{} ", game.turn + 1, if game.acted { "+" } else { "" })?;
+        write_colors(fmt, &game.table.common)?;
+        write!(fmt, " ")?;
+        write_colors(fmt, &game.table.discard)?;
+        for (i, deck) in game.table.tiers.iter().enumerate() {
+            write!(fmt, "{}", if i == 0 { " " } else { "|" })?;
+            write_colors(fmt, deck)?;
+        }
+        write!(fmt, " {}", game.table.monad.len())?;
+        let colors = game.table.colors;
+        if game.turns_played > 0 || *colors != card::SIX_COLORS {
+            write!(fmt, " {}", game.turns_played)?;
+        }
+        if *colors != card::SIX_COLORS {
+            write!(fmt, " {}", colors.name)?;
+        }
+        if game.rules != Rules::default() {
+            write!(fmt, "; {}", game.rules)?;
+        }
+        Ok(())
+    }
+}
+
+impl Game {
+    pub fn position(&self) -> String {
+        Position(self).to_string()
+    }
+
+    /// Reads a position written by `Game::position`, checking that it could really happen.
+    pub fn from_position(source: &str) -> Result<Game, PositionError> {
+        let (source, rules) = match source.split_once(';') {
+            Some((board, rules)) => (board, rules.trim().parse().map_err(PositionError::BadRules)?),
+            None => (source, Rules::default()),
+        };
+
+        // Cards can only be read once the number of tier decks and the colour set at the end say
+        // which ladder and colours they are on.
+        let field_tiers = source.split_whitespace().nth(4).ok_or(PositionError::MissingField("tier decks"))?;
+        let ladder = Ladder::with_tiers(field_tiers.split('|').count())
+            .ok_or_else(|| PositionError::BadTiers(field_tiers.to_string()))?;
+        let colors = match source.split_whitespace().nth(7) {
+            Some(name) => ColorSet::named(name).ok_or_else(|| PositionError::BadColorSet(name.to_string()))?,
+            None => &card::SIX_COLORS,
+        };
+
+        let mut fields = source.split_whitespace();
+        let mut field = |name| fields.next().ok_or(PositionError::MissingField(name));
+
+        let players: Vec<&str> = field("players")?.split('/').collect();
+        let num_players = NumPlayers::from_count(players.len())
+            .map_err(|_| PositionError::PlayerCount(players.len()))?;
+        let mut builder = GameBuilder::new(num_players).ladder(ladder).colors(colors).rules(rules);
+
+        for (i, player) in players.into_iter().enumerate() {
+            let parts: Vec<&str> = player.split(':').collect();
+            let (identity, monads, hand) = match parts.as_slice() {
+                [identity, monads, hand] => (*identity, *monads, *hand),
+                _ => return Err(PositionError::BadPlayer(player.to_string())),
+            };
+
+            let passes = identity.trim_end_matches('>');
+            let passed = identity.len() - passes.len();
+            let took_bonus = passes.ends_with('*');
+            let identity = parse_color(passes.trim_end_matches('*'), colors)?;
+            let hand = hand
+                .split(',')
+                .filter(|card| !card.is_empty())
+                .map(|card| parse_card(card, ladder, colors))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            builder = builder
+                .identity(i, identity)
+                .monads(i, parse_number(monads)?)
+                .took_bonus(i, took_bonus)
+                .passed(i, passed)
+                .hand(i, hand);
+        }
+
+        let turn = field("turn")?;
+        let acted = turn.ends_with('+');
+        match parse_number(turn.trim_end_matches('+'))? {
+            0 => return Err(PositionError::BadNumber(turn.to_string())),
+            number => builder = builder.turn(number - 1, acted),
+        }
+
+        let common = ladder.common();
+        builder = builder
+            .deck(common, parse_colors(field("common")?, common, colors)?)
+            .discard(parse_colors(field("discard")?, common, colors)?);
+
+        for (value, deck) in ladder.values().skip(1).zip(field("tier decks")?.split('|')) {
+            builder = builder.deck(value, parse_colors(deck, value, colors)?);
+        }
+
+        builder = builder.monad_pile(parse_number(field("monads")?)?);
+        if let Some(turns) = fields.next() {
+            builder = builder.turns_played(parse_number(turns)?);
+        }
+        // The colour set was read at the start.
+        fields.next();
+
+        if let Some(extra) = fields.next() {
+            return Err(PositionError::ExtraField(extra.to_string()));
+        }
+
+        builder.build().map_err(PositionError::Build)
+    }
+}
@@ -0,0 +1,191 @@
+use itertools::Itertools;
+use std::{error, fmt, str::FromStr};
+
+use super::{
+    action::ActionError,
+    notation::{Move, NotationError},
+    position::PositionError,
+    team::TeamError,
+    Game,
+    ParseError,
+};
+
+#[derive(Debug)]
+pub enum RecordError {
+    Syntax(String),
+    MissingPosition,
+    Position(PositionError),
+    Rules(ParseError),
+    Teams(TeamError),
+    Notation(usize, NotationError),
+    Illegal(usize, Box<Move>, ActionError),
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::RecordError::*;
+        match self {
+            Syntax(line) => write!(fmt, "Can't read record line: {}", line),
+            MissingPosition => write!(fmt, "The record has no Position header"),
+            Position(error) => write!(fmt, "Bad starting position: {}", error),
+            Rules(error) => write!(fmt, "Unknown rules: {}", error),
+            Teams(error) => write!(fmt, "Can't play this position in teams: {}", error),
+            Notation(turn, error) => write!(fmt, "Turn {}: {}", turn, error),
+            Illegal(turn, action, error) => write!(fmt, "Turn {}: {} is not allowed: {}", turn, action, error),
+        }
+    }
+}
+
+impl error::Error for RecordError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            RecordError::Position(error) => Some(error),
+            RecordError::Rules(error) => Some(error),
+            RecordError::Teams(error) => Some(error),
+            RecordError::Notation(_, error) => Some(error),
+            RecordError::Illegal(_, _, error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// A whole game, PGN style: `[Name "value"]` headers followed by one numbered line per turn,
+/// holding that turn's moves separated by `;`.
+///
+/// ```text
+/// [Players "Player 1 (Blue), Player 2 (Red)"]
+/// [Seed "42"]
+/// [Rules "Standard"]
+/// [Position "Blue:0:Common.Red,... 1 ..."]
+/// [Result "1-0"]
+/// [Termination "All the monads are taken"]
+///
+/// 1. D
+/// 2. T Cm.Rd+Cm.Bl -> Bi.Gr; E
+/// ```
+///
+/// The `Position` header holds the starting position, so a record can be replayed without
+/// knowing how the cards were shuffled, and `Rules` the options it was played with. `Result` is
+/// each seat's monads, or `*` for an unfinished game, and `Termination` says why it ended.
+pub struct Record {
+    pub headers: Vec<(String, String)>,
+    pub turns: Vec<Vec<Move>>,
+    // Whether the last turn has more moves coming.
+    open: bool,
+}
+
+impl Record {
+    pub fn new(start: &Game) -> Self {
+        let mut record = Record { headers: Vec::new(), turns: Vec::new(), open: false };
+        record.set_header("Position", &start.position());
+        record
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_header(&mut self, name: &str, value: &str) {
+        match self.headers.iter_mut().find(|(header, _)| header == name) {
+            Some(header) => header.1 = value.to_string(),
+            None => self.headers.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Adds a move to the current turn. Draws, flips and ends close the turn.
+    pub fn push(&mut self, next: Move) {
+        let closes = matches!(next, Move::Draw | Move::Flip | Move::End);
+        match self.turns.last_mut() {
+            Some(turn) if self.open => turn.push(next),
+            _ => self.turns.push(vec![next]),
+        }
+        self.open = !closes;
+    }
+
+    pub fn finish(&mut self, game: &Game) {
+        match game.end_reason() {
+            Some(reason) => {
+                self.set_header("Result", &game.players.iter().map(|player| player.monads.len()).join("-"));
+                self.set_header("Termination", &reason.to_string());
+            },
+            None => self.set_header("Result", "*"),
+        }
+    }
+
+    /// Plays the record through from its starting position.
+    pub fn replay(&self) -> Result<Game, RecordError> {
+        let position = self.header("Position").ok_or(RecordError::MissingPosition)?;
+        let mut game = Game::from_position(position).map_err(RecordError::Position)?;
+        if let Some(rules) = self.header("Rules") {
+            game.rules = rules.parse().map_err(RecordError::Rules)?;
+            game.check_teams().map_err(RecordError::Teams)?;
+        }
+
+        for (i, turn) in self.turns.iter().enumerate() {
+            for next in turn {
+                let action = next.to_action(&game).map_err(|error| RecordError::Notation(i + 1, error))?;
+                game.perform(action).map_err(|error| RecordError::Illegal(i + 1, Box::new(next.clone()), error))?;
+            }
+        }
+
+        Ok(game)
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (name, value) in &self.headers {
+            writeln!(fmt, "[{} \"{}\"]", name, value.replace('\\', "\\\\").replace('"', "\\\""))?;
+        }
+        writeln!(fmt)?;
+        for (i, turn) in self.turns.iter().enumerate() {
+            writeln!(fmt, "{}. {}", i + 1, turn.iter().join("; "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Record {
+    type Err = RecordError;
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut record = Record { headers: Vec::new(), turns: Vec::new(), open: false };
+        let syntax = |line: &str| RecordError::Syntax(line.to_string());
+
+        for line in source.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if line.starts_with('[') {
+                let inner = line.trim_start_matches('[').trim_end_matches(']');
+                let mut parts = inner.splitn(2, ' ');
+                let name = parts.next().ok_or_else(|| syntax(line))?;
+                let value = parts.next().ok_or_else(|| syntax(line))?.trim();
+                if value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+                    return Err(syntax(line));
+                }
+                let value = value[1..value.len() - 1].replace("\\\"", "\"").replace("\\\\", "\\");
+                record.headers.push((name.to_string(), value));
+                continue;
+            }
+
+            let mut parts = line.splitn(2, ". ");
+            parts.next().and_then(|number| number.parse::<usize>().ok()).ok_or_else(|| syntax(line))?;
+            let turn = record.turns.len() + 1;
+            let moves = parts
+                .next()
+                .ok_or_else(|| syntax(line))?
+                .split(';')
+                .map(|next| next.parse().map_err(|error| RecordError::Notation(turn, error)))
+                .collect::<Result<Vec<Move>, _>>()?;
+            record.turns.push(moves);
+        }
+
+        record.open = record.turns
+            .last()
+            .and_then(|turn| turn.last())
+            .is_some_and(|last| !matches!(last, Move::Draw | Move::Flip | Move::End));
+
+        Ok(record)
+    }
+}
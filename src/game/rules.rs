@@ -0,0 +1,114 @@
+use itertools::Itertools;
+use std::{fmt, str::FromStr};
+
+use super::{expansion::Special, Game, ParseError};
+
+/// Options that change how a game is played or ended.
+#[derive(Clone, Default, PartialEq)]
+pub struct Rules {
+    /// End the game after this many turns, counting every player's.
+    pub turn_limit: Option<usize>,
+    /// Shuffle the discard pile as it is flipped. Each shuffle is seeded from this and the number
+    /// of turns played, so a record still replays exactly.
+    pub shuffle_seed: Option<u64>,
+    /// Flip the discard pile when a draw finds the common deck empty, instead of failing.
+    pub auto_flip: bool,
+    /// Play as two teams of partners sitting opposite each other, who may pass up to this many
+    /// cards to each other a turn.
+    pub teams: Option<usize>,
+    /// The kinds of expansion card mixed into the decks.
+    pub expansion: Vec<&'static Special>,
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let options = self.turn_limit
+            .map(|limit| format!("turn limit {}", limit))
+            .into_iter()
+            .chain(self.shuffle_seed.map(|seed| format!("shuffle on flip {}", seed)))
+            .chain(if self.auto_flip { Some("auto flip".to_string()) } else { None })
+            .chain(self.teams.map(|limit| format!("teams {}", limit)))
+            .chain(if self.expansion.is_empty() {
+                None
+            } else {
+                Some(format!("expansion {}", self.expansion.iter().map(|special| special.name).join(" ")))
+            });
+        write!(fmt, "Standard")?;
+        for option in options {
+            write!(fmt, ", {}", option)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Rules {
+    type Err = ParseError;
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut options = source.split(',').map(str::trim);
+        match options.next() {
+            Some("Standard") => {},
+            other => return Err(ParseError::new("rule set", other.unwrap_or_default())),
+        }
+
+        let mut rules = Rules::default();
+        for option in options {
+            let bad = || ParseError::new("rule option", option);
+            if let Some(limit) = option.strip_prefix("turn limit ") {
+                rules.turn_limit = Some(limit.parse().map_err(|_| bad())?);
+            } else if let Some(seed) = option.strip_prefix("shuffle on flip ") {
+                rules.shuffle_seed = Some(seed.parse().map_err(|_| bad())?);
+            } else if option == "auto flip" {
+                rules.auto_flip = true;
+            } else if let Some(limit) = option.strip_prefix("teams ") {
+                rules.teams = Some(limit.parse().map_err(|_| bad())?);
+            } else if let Some(names) = option.strip_prefix("expansion ") {
+                rules.expansion = names.split_whitespace().map(Special::named).collect::<Option<_>>().ok_or_else(bad)?;
+            } else {
+                return Err(bad());
+            }
+        }
+
+        Ok(rules)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EndReason {
+    MonadsGone,
+    Stalemate,
+    TurnLimit,
+}
+
+impl fmt::Display for EndReason {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::EndReason::*;
+        write!(fmt, "{}", match self {
+            MonadsGone => "All the monads are taken",
+            Stalemate => "Nobody can do anything any more",
+            TurnLimit => "The turn limit was reached",
+        })
+    }
+}
+
+impl Game {
+    /// Why the game is over, if it is.
+    pub fn end_reason(&self) -> Option<EndReason> {
+        if self.table.monad.is_empty() {
+            Some(EndReason::MonadsGone)
+        } else if self.rules.turn_limit.is_some_and(|limit| self.turns_played >= limit) {
+            Some(EndReason::TurnLimit)
+        } else if self.is_stalemate() {
+            Some(EndReason::Stalemate)
+        } else {
+            None
+        }
+    }
+
+    /// Whether there is nothing to draw or flip and no player can do anything else, so every turn
+    /// from now on would just be ended.
+    pub fn is_stalemate(&self) -> bool {
+        self.table.common.is_empty()
+            && self.table.discard.is_empty()
+            && (0..self.players.len()).all(|player| self.check_player_end(player))
+    }
+}
@@ -0,0 +1,138 @@
+use itertools::Itertools;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+    error,
+    fmt,
+};
+
+use super::{
+    goal::Goal,
+    notation::Move,
+    Game,
+};
+
+/// How many positions the solver looks at before giving up.
+pub const MAX_POSITIONS: usize = 100_000;
+
+#[derive(Debug)]
+pub enum SolveError {
+    NoRoute,
+    TooManyPositions,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::SolveError::*;
+        match self {
+            NoRoute => write!(fmt, "There is no way to reach the goal"),
+            TooManyPositions => write!(fmt, "Gave up after looking at {} positions", MAX_POSITIONS),
+        }
+    }
+}
+
+impl error::Error for SolveError {}
+
+/// The fastest way found to a goal: one list of moves per turn, the last one reaching it.
+pub struct Plan {
+    pub turns: Vec<Vec<Move>>,
+}
+
+impl Plan {
+    pub fn moves(&self) -> usize {
+        self.turns.iter().map(Vec::len).sum()
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (i, turn) in self.turns.iter().enumerate() {
+            writeln!(fmt, "{}. {}", i + 1, turn.iter().join("; "))?;
+        }
+        Ok(())
+    }
+}
+
+struct Node {
+    game: Game,
+    // The node this one was reached from, and how.
+    parent: Option<(usize, Move)>,
+    turns: usize,
+}
+
+impl Game {
+    /// Searches for the fewest turns, then the fewest moves, that reach `goal` for the current player.
+    ///
+    /// Every deck is taken to be known, as it is in a puzzle or when looking back over a game,
+    /// and the other players are assumed to pass, so the plan only holds as long as they leave
+    /// the table alone.
+    pub fn solve(&self, goal: &Goal) -> Result<Plan, SolveError> {
+        let player = self.turn;
+
+        let mut nodes = vec![Node { game: self.clone(), parent: None, turns: 1 }];
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((1, 0, 0)));
+
+        while let Some(Reverse((turns, moves, index))) = queue.pop() {
+            // Positions take in every deck and the state of the turn, so equal ones play out the same.
+            if !seen.insert(nodes[index].game.position()) {
+                continue;
+            }
+            if seen.len() > MAX_POSITIONS {
+                return Err(SolveError::TooManyPositions);
+            }
+
+            let game = nodes[index].game.clone();
+            for action in game.legal_actions() {
+                if !goal.allows(&action) {
+                    continue;
+                }
+                let mut next = game.clone();
+                let outcome = match next.perform(action.clone()) {
+                    Ok(outcome) => outcome,
+                    Err(_) => continue,
+                };
+                let played = Move::record(&game, &next, player, &action, &outcome);
+
+                // A draw ends the turn but can still meet the goal on the last one, so the goal comes
+                // before the turn count.
+                let done = goal.met(self, &next, player);
+                let ended = next.turn != player;
+                let (next_turns, next_moves) = if ended { (turns + 1, moves + 1) } else { (turns, moves + 1) };
+                if ended {
+                    if next_turns > goal.turns && !done {
+                        continue;
+                    }
+                    next.skip_to(player);
+                }
+
+                nodes.push(Node { game: next, parent: Some((index, played)), turns: next_turns });
+                if done {
+                    return Ok(plan(&nodes, nodes.len() - 1));
+                }
+                queue.push(Reverse((next_turns, next_moves, nodes.len() - 1)));
+            }
+        }
+
+        Err(SolveError::NoRoute)
+    }
+}
+
+fn plan(nodes: &[Node], mut index: usize) -> Plan {
+    let mut moves = Vec::new();
+    while let Some((parent, played)) = &nodes[index].parent {
+        moves.push((nodes[*parent].turns, played.clone()));
+        index = *parent;
+    }
+    moves.reverse();
+
+    let turns = moves
+        .into_iter()
+        .group_by(|(turn, _)| *turn)
+        .into_iter()
+        .map(|(_, turn)| turn.map(|(_, played)| played).collect())
+        .collect();
+
+    Plan { turns }
+}
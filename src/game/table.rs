@@ -1,70 +1,76 @@
 use crate::game::{
     NumPlayers,
-    card::{self, Monad, Card, Deck},
+    card::{self, Monad, Card, ColorSet, Deck, Ladder},
 };
+use rand::Rng;
 use std::{
     fmt,
     iter::repeat_with,
 };
 
+pub const MONADS: usize = 12;
+
+#[derive(Clone, PartialEq)]
 pub struct Table {
     pub discard: Deck,
     pub common:  Deck,
-    pub bi:      Deck,
-    pub tri:     Deck,
-    pub quad:    Deck,
-    pub quint:   Deck,
+    /// A deck for each tier of the ladder above Common, lowest first.
+    pub tiers:   Vec<Deck>,
     pub monad:   Vec<Monad>,
+    pub ladder:  &'static Ladder,
+    pub colors:  &'static ColorSet,
 }
 
 impl Table {
-    pub fn new(players: NumPlayers) -> Self {
-        let mut table = Table {
-            discard: Deck::multiple(players as usize),
-            common:  Deck::multiple(players as usize),
-            bi:      Deck::multiple(1),
-            tri:     Deck::multiple(1),
-            quad:    Deck::multiple(1),
-            quint:   Deck::multiple(1),
-            monad:   repeat_with(|| Monad).take(12).collect(),
-        };
+    pub fn new(
+        players: NumPlayers,
+        ladder: &'static Ladder,
+        colors: &'static ColorSet,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let mut table = Table::sorted(players, ladder, colors);
+        table.shuffle_decks(rng);
+        table
+    }
 
-        for &color in &card::COLORS {
-            use self::card::Value::*;
+    /// A table with every card in its own deck, in colour order, and a full monad pile.
+    pub fn sorted(players: NumPlayers, ladder: &'static Ladder, colors: &'static ColorSet) -> Self {
+        let mut table = Table::empty(ladder, colors);
 
-            table.common.extend(
-                repeat_with(|| Card { value: Common, color }).take(players as usize)
-            );
-            table.bi   .push(Card { value: Bi   , color });
-            table.tri  .push(Card { value: Tri  , color });
-            table.quad .push(Card { value: Quad , color });
-            table.quint.push(Card { value: Quint, color });
+        for value in ladder.values() {
+            let copies = value.tier().copies.unwrap_or(players as usize);
+            for color in colors.colors() {
+                table.deck_mut(value).extend(repeat_with(|| Card::new(value, color)).take(copies));
+            }
         }
-
-        table.shuffle_decks();
+        table.monad = repeat_with(|| Monad).take(MONADS).collect();
 
         table
     }
 
+    /// A table with no cards or monads at all.
+    pub fn empty(ladder: &'static Ladder, colors: &'static ColorSet) -> Self {
+        Table {
+            discard: Deck::default(),
+            common:  Deck::default(),
+            tiers:   ladder.tiers[1..].iter().map(|_| Deck::default()).collect(),
+            monad:   Vec::new(),
+            ladder,
+            colors,
+        }
+    }
+
     pub fn deck_mut(&mut self, value: card::Value) -> &mut Deck {
-        use self::card::Value::*;
-        match value {
-            Common => &mut self.common,
-            Bi     => &mut self.bi,
-            Tri    => &mut self.tri,
-            Quad   => &mut self.quad,
-            Quint  => &mut self.quint,
+        match value.rank() {
+            0    => &mut self.common,
+            rank => &mut self.tiers[rank - 1],
         }
     }
 
     pub fn deck(&self, value: card::Value) -> &Deck {
-        use self::card::Value::*;
-        match value {
-            Common => &self.common,
-            Bi     => &self.bi,
-            Tri    => &self.tri,
-            Quad   => &self.quad,
-            Quint  => &self.quint,
+        match value.rank() {
+            0    => &self.common,
+            rank => &self.tiers[rank - 1],
         }
     }
 
@@ -73,38 +79,27 @@ impl Table {
     }
 
     pub fn return_card(&mut self, card: Card) {
-        use self::card::Value::Common;
         match card.value {
-            Common => &mut self.discard,
-            value  => self.deck_mut(value),
+            value if value.is_common() => &mut self.discard,
+            value => self.deck_mut(value),
         }.insert(0, card);
     }
 
-    fn shuffle_decks(&mut self) {
-        self.bi    .shuffle();
-        self.tri   .shuffle();
-        self.quad  .shuffle();
-        self.quint .shuffle();
-        self.common.shuffle();
+    fn shuffle_decks(&mut self, rng: &mut impl Rng) {
+        for deck in &mut self.tiers {
+            deck.shuffle(rng);
+        }
+        self.common.shuffle(rng);
     }
 }
 
 impl fmt::Display for Table {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            fmt,
-            "Common:  {}\n\
-            Discard: {}\n\
-            Bi:      {}\n\
-            Tri:     {}\n\
-            Quad:    {}\n\
-            Quint:   {}\n",
-            self.common.len(),
-            self.discard,
-            self.bi,
-            self.tri,
-            self.quad,
-            self.quint,
-        )
+        writeln!(fmt, "{:<8} {}", "Common:", self.common.len())?;
+        writeln!(fmt, "{:<8} {}", "Discard:", self.discard)?;
+        for (value, deck) in self.ladder.values().skip(1).zip(&self.tiers) {
+            writeln!(fmt, "{:<8} {}", format!("{:?}:", value), deck)?;
+        }
+        Ok(())
     }
 }
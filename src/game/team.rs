@@ -0,0 +1,128 @@
+use rand::{seq::SliceRandom, Rng};
+use std::{error, fmt};
+
+use super::{
+    card::{Color, ColorSet, Ladder},
+    Game,
+    SelectionError,
+};
+
+/// Reasons a game can't be played in teams.
+#[derive(Debug)]
+pub enum TeamError {
+    Players(usize),
+    NotPartners(Color, Color),
+}
+
+impl fmt::Display for TeamError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::TeamError::*;
+        match self {
+            Players(count)            => write!(fmt, "Teams need 4 players, not {}", count),
+            NotPartners(first, other) => write!(fmt, "{} and {} aren't a bonus pair, so they can't be partners", first, other),
+        }
+    }
+}
+
+impl error::Error for TeamError {}
+
+#[derive(Debug)]
+pub enum PassError {
+    Selection(SelectionError),
+    NoPartner,
+    LimitReached(usize),
+}
+
+impl fmt::Display for PassError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::PassError::*;
+        match self {
+            Selection(error)    => write!(fmt, "{}", error),
+            NoPartner           => write!(fmt, "You don't have a partner to pass to"),
+            LimitReached(limit) => write!(fmt, "You can only pass {} card(s) a turn", limit),
+        }
+    }
+}
+
+impl error::Error for PassError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            PassError::Selection(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Identities for two teams from `colors`: two bonus pairs picked at random, with each pair's
+/// colours sitting opposite each other.
+pub fn team_identities(colors: &'static ColorSet, rng: &mut impl Rng) -> Vec<Color> {
+    let mut pairs = colors
+        .colors()
+        .filter(|color| color.index() < color.bonus_partner().index())
+        .collect::<Vec<_>>();
+    pairs.shuffle(rng);
+    let mut seat = |color: Color| if rng.gen() { color } else { color.bonus_partner() };
+    let (first, second) = (seat(pairs[0]), seat(pairs[1]));
+    vec![first, second, first.bonus_partner(), second.bonus_partner()]
+}
+
+impl Game {
+    /// Deals a four player game of two teams, partners sitting opposite each other with the
+    /// colours of a bonus pair, who may pass up to `pass_limit` cards to each other a turn.
+    pub fn with_teams(pass_limit: usize, ladder: &'static Ladder, colors: &'static ColorSet, rng: &mut impl Rng) -> Self {
+        let identities = team_identities(colors, rng);
+        let mut game = Game::with_identities(&identities, ladder, colors, rng)
+            .expect("Two bonus pairs are four different colours");
+        game.rules.teams = Some(pass_limit);
+        game
+    }
+
+    /// The seat opposite `player`, when playing in teams.
+    pub fn partner(&self, player: usize) -> Option<usize> {
+        match self.rules.teams {
+            Some(_) if self.players.len() == 4 => Some((player + 2) % 4),
+            _ => None,
+        }
+    }
+
+    /// The monads held by `player`'s side of the table: theirs and their partner's.
+    pub fn score(&self, player: usize) -> usize {
+        let partner = self.partner(player).map(|partner| self.players[partner].monads.len());
+        self.players[player].monads.len() + partner.unwrap_or(0)
+    }
+
+    /// Checks that a game played in teams has four players, with partners holding bonus pairs.
+    pub fn check_teams(&self) -> Result<(), TeamError> {
+        if self.rules.teams.is_none() {
+            return Ok(());
+        }
+        if self.players.len() != 4 {
+            return Err(TeamError::Players(self.players.len()));
+        }
+        for player in 0..2 {
+            let first = self.players[player].identity;
+            let other = self.players[player + 2].identity;
+            if first.bonus_partner() != other {
+                return Err(TeamError::NotPartners(first, other));
+            }
+        }
+        Ok(())
+    }
+
+    /// Hands `card` from `player`'s hand to their partner's.
+    pub fn pass(&mut self, player: usize, card: usize) -> Result<(), PassError> {
+        let partner = self.partner(player).ok_or(PassError::NoPartner)?;
+        let limit = self.rules.teams.unwrap_or(0);
+        let passer = &mut self.players[player];
+        passer.check_selection(&[card]).map_err(PassError::Selection)?;
+        if passer.passed >= limit {
+            return Err(PassError::LimitReached(limit));
+        }
+
+        let card = passer.hand.remove(card);
+        passer.passed += 1;
+        self.players[partner].hand.push(card);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,726 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{
+    action::Action,
+    card::{self, Card, ValueOrMonad},
+    notation::Move,
+    payment::PaymentPreference,
+    record::Record,
+    Game,
+    NumPlayers,
+};
+
+const TABLES: [NumPlayers; 3] = [NumPlayers::Two, NumPlayers::Three, NumPlayers::Four];
+
+/// A few indexes that may point past the end of the hand or repeat each other.
+fn random_indexes(rng: &mut StdRng, hand: usize, count: usize) -> Vec<usize> {
+    (0..count).map(|_| rng.gen_range(0, hand + 2)).collect()
+}
+
+fn random_target(rng: &mut StdRng, game: &Game) -> ValueOrMonad {
+    let ladder = game.table.ladder;
+    match ladder.values().nth(rng.gen_range(0, ladder.tiers.len() + 1)) {
+        Some(value) => ValueOrMonad::Value(value),
+        None => ValueOrMonad::Monad,
+    }
+}
+
+/// Mostly nonsense, with the occasional legal action mixed in so the game actually moves along.
+fn random_action(rng: &mut StdRng, game: &Game) -> Action {
+    let hand = game.players[game.turn].hand.len();
+    match rng.gen_range(0, 8) {
+        0 => Action::Draw,
+        1 => Action::Flip,
+        2 => Action::End,
+        3 => Action::Trade {
+            card1: rng.gen_range(0, hand + 2),
+            card2: rng.gen_range(0, hand + 2),
+            bonus: rng.gen(),
+        },
+        4 => {
+            let count = rng.gen_range(0, 5);
+            Action::Buy { cards: random_indexes(rng, hand, count), target: random_target(rng, game) }
+        },
+        5 => {
+            let count = rng.gen_range(3, 8);
+            Action::Leap { cards: random_indexes(rng, hand, count) }
+        },
+        _ => {
+            let legal = game.legal_actions();
+            if legal.is_empty() { Action::Draw } else { legal[rng.gen_range(0, legal.len())].clone() }
+        },
+    }
+}
+
+/// Plays up to `moves` random legal actions, checking the game after each, then that the record of
+/// them replays to the same game and the final position reads back.
+fn play_out(game: &mut Game, rng: &mut StdRng, moves: usize) -> Record {
+    let mut record = Record::new(game);
+    record.set_header("Rules", &game.rules.to_string());
+
+    for _ in 0..moves {
+        let legal = game.legal_actions();
+        if legal.is_empty() {
+            break;
+        }
+        let action = legal[rng.gen_range(0, legal.len())].clone();
+        let before = game.clone();
+        let player = game.turn;
+        let outcome = game.perform(action.clone()).expect("Legal actions can be performed");
+        assert!(game.validate().is_ok(), "{} broke an invariant", action);
+        record.push(Move::record(&before, game, player, &action, &outcome));
+    }
+
+    let replayed = record.to_string().parse::<Record>().and_then(|record| record.replay()).ok();
+    assert!(replayed.is_some_and(|replayed| replayed == *game), "the record doesn't replay");
+    let parsed = Game::from_position(&game.position()).ok();
+    assert!(parsed.is_some_and(|parsed| parsed == *game), "{} reads back differently", game.position());
+    record
+}
+
+#[test]
+fn random_actions_keep_the_game_consistent() {
+    for seed in 0..60 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Game::with_rng(TABLES[seed as usize % TABLES.len()], &mut rng);
+
+        for _ in 0..400 {
+            let action = random_action(&mut rng, &game);
+            let before = game.clone();
+
+            if game.perform(action.clone()).is_err() {
+                assert!(game == before, "seed {}: failed {} changed the game", seed, action);
+            }
+
+            if let Err(error) = game.validate() {
+                panic!("seed {}: {} broke an invariant: {}", seed, action, error);
+            }
+        }
+    }
+}
+
+#[test]
+fn legal_actions_always_succeed() {
+    for seed in 0..30 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Game::with_rng(TABLES[seed as usize % TABLES.len()], &mut rng);
+
+        for _ in 0..300 {
+            if game.is_over() {
+                break;
+            }
+            let legal = game.legal_actions();
+            if legal.is_empty() {
+                game.perform(Action::Draw).ok();
+                continue;
+            }
+            let action = legal[rng.gen_range(0, legal.len())].clone();
+            assert!(game.perform(action.clone()).is_ok(), "seed {}: legal {} failed", seed, action);
+        }
+
+        let monads = game.table.monad.len() + game.players.iter().map(|p| p.monads.len()).sum::<usize>();
+        assert_eq!(monads, super::table::MONADS);
+    }
+}
+
+#[test]
+fn preview_matches_the_real_thing() {
+    for seed in 0..30 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Game::with_rng(NumPlayers::Three, &mut rng);
+
+        for _ in 0..200 {
+            let action = random_action(&mut rng, &game);
+            let player = game.turn;
+            let before = game.clone();
+
+            match (game.preview(player, &action), game.act(player, action.clone())) {
+                (Ok(preview), Ok(outcome)) => {
+                    let hand = &game.players[player].hand;
+                    assert_eq!(preview.points_after, hand.iter().map(Card::num).sum::<usize>());
+                    assert_eq!(preview.drawn.len(), outcome.cards);
+                    assert_eq!(preview.monads, outcome.monads);
+                },
+                (Err(_), Err(_)) => assert!(game == before),
+                _ => panic!("seed {}: preview and act disagree about {}", seed, action),
+            }
+        }
+    }
+}
+
+#[test]
+fn optimal_payment_is_optimal() {
+    for seed in 0..200 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Game::with_rng(NumPlayers::Four, &mut rng);
+
+        // Hand out a random assortment of tier cards to make things interesting.
+        for _ in 0..rng.gen_range(0, 6) {
+            let value = game.table.ladder.values().nth(rng.gen_range(1, game.table.ladder.tiers.len())).unwrap();
+            game.players[0].draw_card(value, &mut game.table);
+        }
+
+        let target = random_target(&mut rng, &game);
+        let hand = &game.players[0].hand;
+        let cost = game.table.ladder.price(target);
+
+        let eligible: Vec<usize> = (0..hand.len()).filter(|&i| hand[i].num() < cost).collect();
+        let best = (0..1usize << eligible.len())
+            .map(|mask| {
+                (0..eligible.len())
+                    .filter(|bit| mask & (1 << bit) != 0)
+                    .map(|bit| hand[eligible[bit]].num())
+                    .sum::<usize>()
+            })
+            .filter(|&sum| sum >= cost)
+            .min();
+
+        match game.optimal_payment(0, target, PaymentPreference::keep_all()) {
+            Ok(mut cards) => {
+                let paid: usize = cards.iter().map(|&i| hand[i].num()).sum();
+                assert_eq!(Some(paid), best, "seed {}", seed);
+                assert!(game.clone().buy(0, &mut cards, target).is_ok(), "seed {}", seed);
+            },
+            Err(_) => {
+                let out_of_cards = match target {
+                    ValueOrMonad::Value(value) => game.table.deck(value).is_empty(),
+                    ValueOrMonad::Monad => false,
+                };
+                assert!(best.is_none() || out_of_cards, "seed {}: a payment exists", seed);
+            },
+        }
+    }
+}
+
+#[test]
+fn payment_preferences_keep_or_spend_bonus_pairs() {
+    use super::builder::GameBuilder;
+    use super::card::{Color, Value};
+    use super::{expansion::PRISM, rules::Rules};
+
+    let commons = [Color::Orange, Color::Yellow, Color::Green, Color::Blue];
+    let mut hand = vec![Card::new(Value::Bi, Color::Red), Card::new(Value::Bi, Color::Purple)];
+    hand.extend(commons.iter().map(|&color| Card::new(Value::Common, color)));
+    let game = GameBuilder::new(NumPlayers::Two).hand(0, hand).build().expect("consistent position");
+    let tri = ValueOrMonad::Value(Value::Tri);
+
+    // Both pay exactly 7, but keeping the red and purple pair means spending every common instead.
+    let keep = game.optimal_payment(0, tri, "keep".parse().unwrap()).unwrap();
+    assert_eq!(keep.len(), 5);
+    assert_eq!(game.optimal_payment(0, tri, "spend".parse().unwrap()).unwrap(), vec![0, 1, 2]);
+    assert!("hoard".parse::<PaymentPreference>().is_err());
+
+    // A Prism pairs with the red too, so it is kept like the purple was.
+    let prism = Card { special: Some(&PRISM), ..Card::new(Value::Bi, Color::Green) };
+    let mut hand = vec![Card::new(Value::Bi, Color::Red), prism];
+    hand.extend(commons.iter().map(|&color| Card::new(Value::Common, color)));
+    let game = GameBuilder::new(NumPlayers::Two)
+        .rules(Rules { expansion: vec![&PRISM], ..Rules::default() })
+        .hand(0, hand)
+        .build()
+        .expect("consistent position");
+    assert!(game.players[0].in_bonus_pair(1));
+    assert_eq!(game.optimal_payment(0, tri, "keep".parse().unwrap()).unwrap().len(), 5);
+}
+
+#[test]
+fn builder_sets_up_exact_positions() {
+    use super::builder::{BuildError, GameBuilder};
+    use super::card::{Color, Value};
+    use super::position::PositionError;
+
+    let game = GameBuilder::new(NumPlayers::Two)
+        .identity(0, Color::Blue)
+        .identity(1, Color::Red)
+        .hand(0, vec![Card::new(Value::Quint, Color::Red), Card::new(Value::Quint, Color::Blue)])
+        .deck(Value::Tri, Vec::new())
+        .discard(vec![Card::new(Value::Tri, Color::Red)])
+        .monads(1, 3)
+        .turn(1, false)
+        .build();
+    assert!(game.is_err(), "Tri cards can't be in the discard pile");
+
+    let tri = card::SIX_COLORS.colors().map(|color| Card::new(Value::Tri, color)).collect::<Vec<_>>();
+    let game = GameBuilder::new(NumPlayers::Two)
+        .identity(0, Color::Blue)
+        .hand(0, vec![Card::new(Value::Quint, Color::Red), Card::new(Value::Quint, Color::Blue)])
+        .hand(1, tri)
+        .deck(Value::Tri, Vec::new())
+        .monads(1, 3)
+        .turn(1, false)
+        .build()
+        .expect("consistent position");
+
+    assert_eq!(game.turn, 1);
+    assert_eq!(game.table.monad.len(), super::table::MONADS - 3);
+    assert!(game.table.deck(Value::Tri).is_empty());
+    assert_eq!(game.table.deck(Value::Quint).len(), card::SIX_COLORS.hues.len() - 2);
+    assert_eq!(game.table.common.len(), card::SIX_COLORS.hues.len() * 2);
+    assert!(GameBuilder::new(NumPlayers::Two).turn(2, false).build().is_err());
+
+    let twins = GameBuilder::new(NumPlayers::Three).identity(2, Color::Red).build();
+    assert!(matches!(twins, Err(BuildError::IdentityTaken(color)) if color == Color::Red));
+    let position = GameBuilder::new(NumPlayers::Two).build().expect("consistent position").position();
+    let twins = Game::from_position(&position.replacen("Orange:", "Red:", 1));
+    assert!(matches!(twins, Err(PositionError::Build(BuildError::IdentityTaken(_)))));
+}
+
+#[test]
+fn positions_round_trip() {
+    for seed in 0..40 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Game::with_rng(TABLES[seed as usize % TABLES.len()], &mut rng);
+
+        for _ in 0..100 {
+            let action = random_action(&mut rng, &game);
+            game.perform(action).ok();
+
+            let position = game.position();
+            match Game::from_position(&position) {
+                Ok(parsed) => assert!(parsed == game, "seed {}: {} read back differently", seed, position),
+                Err(error) => panic!("seed {}: {} didn't read back: {}", seed, position, error),
+            }
+        }
+    }
+
+    assert!(Game::from_position("Blue:0: 1 - - -|-|-|- 12").is_err());
+    assert!(Game::from_position("").is_err());
+}
+
+#[test]
+fn positions_carry_their_rules() {
+    use super::{expansion::SPECIALS, position::PositionError};
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut teams = Game::with_teams(2, &card::STANDARD, &card::SIX_COLORS, &mut rng);
+    teams.rules.turn_limit = Some(40);
+    teams.rules.shuffle_seed = Some(7);
+    teams.rules.auto_flip = true;
+    let mut expansion = Game::with_rng(NumPlayers::Three, &mut rng);
+    expansion.add_expansion(SPECIALS.to_vec(), &mut rng);
+
+    for game in [teams, expansion] {
+        let position = game.position();
+        assert!(position.contains("; Standard, "), "{} leaves out the rules", position);
+        match Game::from_position(&position) {
+            Ok(parsed) => assert!(parsed == game, "{} read back differently", position),
+            Err(error) => panic!("{} didn't read back: {}", position, error),
+        }
+    }
+
+    let standard = Game::with_rng(NumPlayers::Two, &mut rng).position();
+    assert!(!standard.contains(';'));
+    let unknown = Game::from_position(&format!("{}; Standard, sudden death", standard));
+    assert!(matches!(unknown, Err(PositionError::BadRules(_))));
+}
+
+#[test]
+fn records_replay_to_the_same_game() {
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Game::with_rng(TABLES[seed as usize % TABLES.len()], &mut rng);
+        let mut record = Record::new(&game);
+
+        for _ in 0..200 {
+            let legal = game.legal_actions();
+            if legal.is_empty() {
+                break;
+            }
+            let action = legal[rng.gen_range(0, legal.len())].clone();
+            let before = game.clone();
+            let player = game.turn;
+            if let Ok(outcome) = game.perform(action.clone()) {
+                let played = Move::record(&before, &game, player, &action, &outcome);
+                match played.to_string().parse::<Move>() {
+                    Ok(parsed) => assert!(parsed == played, "seed {}: {} read back differently", seed, played),
+                    Err(error) => panic!("seed {}: {} didn't read back: {}", seed, played, error),
+                }
+                record.push(played);
+            }
+        }
+        record.finish(&game);
+
+        let text = record.to_string();
+        let replayed = match text.parse::<Record>().and_then(|record| record.replay()) {
+            Ok(replayed) => replayed,
+            Err(error) => panic!("seed {}: {}\n{}", seed, error, text),
+        };
+        assert!(replayed == game, "seed {}: replay ended somewhere else\n{}", seed, text);
+    }
+}
+
+#[test]
+fn solver_finds_the_shortest_route() {
+    use super::{builder::GameBuilder, goal::Goal};
+    use super::card::{Color, Value};
+
+    let quints = vec![Color::Blue, Color::Purple, Color::Yellow, Color::Orange, Color::Red]
+        .into_iter()
+        .map(|color| Card::new(Value::Quint, color))
+        .collect::<Vec<_>>();
+    let game = GameBuilder::new(NumPlayers::Two)
+        .identity(0, Color::Blue)
+        .hand(0, vec![
+            Card::new(Value::Quad, Color::Red),
+            Card::new(Value::Common, Color::Yellow),
+            Card::new(Value::Quad, Color::Blue),
+            Card::new(Value::Quint, Color::Green),
+        ])
+        .deck(Value::Quint, quints)
+        .build()
+        .expect("consistent position");
+
+    let plan = game.solve(&Goal::monad(2)).expect("a route to a monad");
+    assert_eq!(plan.turns.len(), 1);
+    assert_eq!(plan.moves(), 2);
+
+    let mut replayed = game.clone();
+    for next in &plan.turns[0] {
+        let action = next.to_action(&replayed).expect("a move in the hand");
+        assert!(replayed.perform(action).is_ok(), "{} should be legal", next);
+    }
+    assert_eq!(replayed.players[0].monads.len(), 1);
+
+    let stuck = GameBuilder::new(NumPlayers::Two)
+        .hand(0, vec![Card::new(Value::Common, Color::Red)])
+        .build()
+        .expect("consistent position");
+    assert!(stuck.solve(&Goal::monad(1)).is_err());
+
+    // With nothing in hand, the only way to a Common is the draw that ends the last turn.
+    let empty = GameBuilder::new(NumPlayers::Two).build().expect("consistent position");
+    let goal = Goal { target: ValueOrMonad::Value(Value::Common), turns: 1, draws: true };
+    let plan = empty.solve(&goal).expect("a draw on the last turn");
+    assert_eq!(plan.to_string(), "1. D\n");
+    assert!(empty.solve(&Goal { draws: false, ..goal }).is_err());
+}
+
+#[test]
+fn games_end_on_stalemate_or_turn_limit() {
+    use super::{action::ActionError, rules::EndReason};
+    use super::card::{Color, Value};
+
+    let mut game = Game::with_rng(NumPlayers::Two, &mut StdRng::seed_from_u64(0));
+    game.rules.turn_limit = Some(3);
+    while !game.is_over() {
+        // Ending the turn comes last once it is allowed.
+        let action = game.legal_actions().pop().expect("something to do");
+        assert!(game.perform(action).is_ok());
+    }
+    assert_eq!(game.turns_played, 3);
+    assert_eq!(game.end_reason(), Some(EndReason::TurnLimit));
+    assert!(matches!(game.perform(Action::Draw), Err(ActionError::GameOver)));
+    assert!(game.legal_actions().is_empty());
+    assert!(Game::from_position(&game.position()).ok().is_some_and(|parsed| parsed.turns_played == 3));
+
+    // Nothing left to draw or flip, and nothing anyone can do with their hand. No real game gets
+    // here, since the missing cards would have to be somewhere.
+    let mut game = Game::with_rng(NumPlayers::Two, &mut StdRng::seed_from_u64(0));
+    for value in card::STANDARD.values() {
+        game.table.deck_mut(value).clear();
+    }
+    game.table.discard.clear();
+    game.players[0].hand = vec![Card::new(Value::Common, Color::Red)].into();
+    game.players[1].hand = vec![Card::new(Value::Common, Color::Orange)].into();
+    assert_eq!(game.end_reason(), Some(EndReason::Stalemate));
+
+    // A Bi to trade for is enough to get going again.
+    game.players[1].hand.push(Card::new(Value::Common, Color::Blue));
+    game.table.deck_mut(Value::Bi).push(Card::new(Value::Bi, Color::Red));
+    assert_eq!(game.end_reason(), None);
+}
+
+#[test]
+fn drawing_from_an_empty_common_deck() {
+    use super::{action::ActionError, builder::GameBuilder, rules::Rules};
+    use super::card::{Color, Value};
+
+    let discard = vec![Color::Red, Color::Orange, Color::Yellow, Color::Purple, Color::Blue, Color::Green]
+        .into_iter()
+        .map(|color| Card::new(Value::Common, color))
+        .collect::<Vec<_>>();
+    let game = GameBuilder::new(NumPlayers::Two)
+        .hand(0, vec![Card::new(Value::Common, Color::Red)])
+        .hand(1, discard[1..].to_vec())
+        .deck(Value::Common, Vec::new())
+        .discard(discard.clone())
+        .build()
+        .expect("consistent position");
+
+    // A draw that fails leaves the turn alone.
+    let mut plain = game.clone();
+    assert!(matches!(plain.perform(Action::Draw), Err(ActionError::Draw(super::DrawError { discarded: 5 | 6 }))));
+    assert!(plain == game);
+
+    let mut auto = game.clone();
+    auto.rules.auto_flip = true;
+    assert!(auto.perform(Action::Draw).is_ok());
+    assert_eq!(auto.turn, 1);
+    assert_eq!(auto.players[0].hand.len(), 2);
+    assert!(auto.table.discard.is_empty());
+
+    let flipped = |seed| {
+        let mut game = game.clone();
+        game.rules.shuffle_seed = seed;
+        assert!(game.perform(Action::Flip).is_ok());
+        game.table.common.to_vec()
+    };
+    assert!(flipped(None) == discard);
+    assert!(flipped(Some(1)) == flipped(Some(1)));
+    assert!((0..8).any(|seed| flipped(Some(seed)) != discard));
+
+    let rules = Rules { turn_limit: Some(40), shuffle_seed: Some(7), auto_flip: true, ..Rules::default() };
+    assert!(rules.to_string().parse::<Rules>() == Ok(rules.clone()));
+    assert!("Standard".parse::<Rules>() == Ok(Rules::default()));
+}
+
+#[test]
+fn identity_policies_hand_out_different_colours() {
+    use super::{card::{Color, Temp}, identity::Identities};
+
+    for &num_players in &TABLES {
+        for seed in 0..50 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            for &policy in &[Identities::Random, Identities::Balanced, Identities::Seats] {
+                let game = Game::with_policy(num_players, policy, &card::STANDARD, &card::SIX_COLORS, &mut rng).expect("not a draft");
+                let colors = game.players.iter().map(|player| player.identity).collect::<Vec<_>>();
+                assert_eq!(colors.len(), num_players as usize);
+                assert!(colors.iter().enumerate().all(|(i, color)| !colors[..i].contains(color)), "{:?}", colors);
+
+                if policy != Identities::Random {
+                    let warm = colors.iter().filter(|color| color.temp() == Temp(0)).count();
+                    assert!(warm * 2 + 1 >= colors.len() && warm * 2 <= colors.len() + 1, "{:?}", colors);
+                }
+            }
+        }
+    }
+
+    let seats = Identities::Seats.assign(NumPlayers::Four, &card::SIX_COLORS, &mut StdRng::seed_from_u64(0));
+    assert_eq!(seats, Some(vec![Color::Red, Color::Purple, Color::Orange, Color::Blue]));
+    assert!(Identities::Draft.assign(NumPlayers::Two, &card::SIX_COLORS, &mut StdRng::seed_from_u64(0)).is_none());
+    let mut rng = StdRng::seed_from_u64(0);
+    let repeated = [Color::Red, Color::Blue, Color::Red];
+    assert!(Game::with_identities(&repeated, &card::STANDARD, &card::SIX_COLORS, &mut rng).is_err());
+    assert!(Game::with_identities(&[Color::Red], &card::STANDARD, &card::SIX_COLORS, &mut rng).is_err());
+}
+
+#[test]
+fn six_tier_ladders_play_out() {
+    use super::{builder::GameBuilder, card::{Color, Value, SIX_TIERS}, identity::Identities};
+
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Game::with_policy(NumPlayers::Three, Identities::Balanced, &SIX_TIERS, &card::SIX_COLORS, &mut rng).unwrap();
+        assert_eq!(game.table.tiers.len(), 5);
+        play_out(&mut game, &mut rng, 200);
+    }
+
+    let sext = SIX_TIERS.value("Sext").unwrap();
+    let mut game = GameBuilder::new(NumPlayers::Two)
+        .ladder(&SIX_TIERS)
+        .identity(0, Color::Green)
+        .hand(0, vec![Card::new(Value::Quint, Color::Red), Card::new(Value::Quint, Color::Blue)])
+        .build()
+        .unwrap();
+    assert!(game.perform(Action::Trade { card1: 0, card2: 1, bonus: false }).is_ok());
+    assert_eq!(game.players[0].hand[0].value, sext);
+    assert_eq!(game.players[0].monads.len(), 0);
+    assert_eq!(SIX_TIERS.leap(7), Some(sext));
+    assert_eq!(SIX_TIERS.price(ValueOrMonad::Monad), 176);
+}
+
+#[test]
+fn colour_sets_play_out() {
+    use super::{builder::GameBuilder, card::{ColorSet, Value, EIGHT_COLORS, THREE_GROUPS}, identity::Identities};
+
+    for &colors in &[&EIGHT_COLORS, &THREE_GROUPS] {
+        for seed in 0..10 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut game = Game::with_policy(NumPlayers::Four, Identities::Balanced, &card::STANDARD, colors, &mut rng).unwrap();
+            // One common of each colour per player, and six of them dealt to each.
+            assert_eq!(game.table.common.len() + 4 * 6, colors.hues.len() * 4);
+            let temps = game.players.iter().map(|player| player.identity.temp()).collect::<Vec<_>>();
+            assert!(colors.temps().all(|temp| temps.contains(&temp)));
+            play_out(&mut game, &mut rng, 200);
+        }
+    }
+
+    // Yellow and green are both earthy, so they can't be traded together in three groups.
+    let yellow = THREE_GROUPS.color("Yellow").unwrap();
+    let green = THREE_GROUPS.color("Green").unwrap();
+    let mut game = GameBuilder::new(NumPlayers::Two)
+        .colors(&THREE_GROUPS)
+        .identity(0, THREE_GROUPS.color("Red").unwrap())
+        .hand(0, vec![Card::new(Value::Bi, yellow), Card::new(Value::Bi, green)])
+        .build()
+        .unwrap();
+    assert!(game.perform(Action::Trade { card1: 0, card2: 1, bonus: false }).is_err());
+    assert!(ColorSet::named("three") == Some(&THREE_GROUPS));
+}
+
+#[test]
+fn teams_pass_cards_and_share_monads() {
+    use super::{action::ActionError, builder::{BuildError, GameBuilder}, rules::Rules, team::TeamError};
+
+    for seed in 0..10 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Game::with_teams(1, &card::STANDARD, &card::SIX_COLORS, &mut rng);
+        assert!(game.check_teams().is_ok());
+        let record = play_out(&mut game, &mut rng, 300);
+        let passes = |turn: &Vec<Move>| turn.iter().filter(|played| matches!(played, Move::Pass { .. })).count();
+        assert!(record.turns.iter().all(|turn| passes(turn) <= 1), "seed {}", seed);
+        for winner in game.winners() {
+            assert!(game.winners().contains(&game.partner(winner).unwrap()));
+        }
+    }
+
+    let mut game = Game::with_teams(1, &card::STANDARD, &card::SIX_COLORS, &mut StdRng::seed_from_u64(0));
+    let card = game.players[0].hand[0].clone();
+    assert!(game.perform(Action::Pass { card: 0 }).is_ok());
+    assert!(game.players[2].hand.last() == Some(&card));
+    assert!(game.perform(Action::Pass { card: 0 }).is_err());
+    // A pass is an action, so the turn can't become a draw or flip any more, but it can end.
+    assert!(matches!(game.perform(Action::Draw), Err(ActionError::AlreadyActed)));
+    assert!(matches!(game.perform(Action::Flip), Err(ActionError::AlreadyActed)));
+    assert!(game.perform(Action::End).is_ok());
+    game.players[2].monads.push(card::Monad);
+    assert_eq!(game.score(0), 1);
+    assert_eq!(game.winners(), vec![0, 2]);
+
+    let mut game = Game::with_rng(NumPlayers::Four, &mut StdRng::seed_from_u64(0));
+    assert!(game.perform(Action::Pass { card: 0 }).is_err());
+    // The builder seats red opposite yellow, which aren't a bonus pair.
+    let built = GameBuilder::new(NumPlayers::Four).rules(Rules { teams: Some(1), ..Rules::default() }).build();
+    assert!(matches!(built, Err(BuildError::Teams(TeamError::NotPartners(..)))));
+}
+
+#[test]
+fn expansion_cards_change_trades_buys_and_leaps() {
+    use super::{
+        builder::GameBuilder,
+        card::{Color, Value},
+        expansion::{Special, PEEK, PRISM, REFUND, SPECIALS},
+        invariant::InvariantError,
+        rules::Rules,
+    };
+
+    for seed in 0..10 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Game::with_rng(TABLES[seed as usize % TABLES.len()], &mut rng);
+        game.add_expansion(SPECIALS.to_vec(), &mut rng);
+        play_out(&mut game, &mut rng, 200);
+    }
+
+    // The rules say which expansion cards are in play, and only cards of the game's ladder and
+    // colours may be.
+    let mut game = Game::with_rng(NumPlayers::Two, &mut StdRng::seed_from_u64(0));
+    game.add_expansion(vec![&PRISM], &mut StdRng::seed_from_u64(0));
+    assert!(game.validate().is_ok());
+    let mut missing = game.clone();
+    missing.table.deck_mut(Value::Bi).retain(|card| card.special.is_none() || card.color != Color::Red);
+    assert!(matches!(missing.validate(), Err(InvariantError::MissingSpecial(_))));
+    let mut unmixed = game.clone();
+    unmixed.rules.expansion.clear();
+    assert!(matches!(unmixed.validate(), Err(InvariantError::NotInGame(_))));
+    let mut off_ladder = game.clone();
+    off_ladder.players[0].hand.push(Card::new(card::SIX_TIERS.values().last().unwrap(), Color::Red));
+    assert!(matches!(off_ladder.validate(), Err(InvariantError::NotInGame(_))));
+    let mut off_colors = game.clone();
+    off_colors.players[0].hand.push(Card::new(Value::Bi, card::EIGHT_COLORS.colors().last().unwrap()));
+    assert!(matches!(off_colors.validate(), Err(InvariantError::NotInGame(_))));
+
+    let special = |value, color, special: &'static Special| Card { value, color, special: Some(special) };
+    let mixing_in = |special: &'static Special| Rules { expansion: vec![special], ..Rules::default() };
+
+    // Red and orange are both warm, but a prism trades as blue next to the orange.
+    let mut game = GameBuilder::new(NumPlayers::Two)
+        .rules(mixing_in(&PRISM))
+        .hand(0, vec![special(Value::Bi, Color::Red, &PRISM), Card::new(Value::Bi, Color::Orange)])
+        .build()
+        .unwrap();
+    assert!(game.players[0].can_take_bonus(0, 1));
+    assert!(game.perform(Action::Trade { card1: 0, card2: 1, bonus: true }).is_ok());
+
+    // Seventeen points for a sixteen point Quad gives one common back.
+    let mut game = GameBuilder::new(NumPlayers::Two)
+        .rules(mixing_in(&REFUND))
+        .hand(0, vec![
+            special(Value::Tri, Color::Red, &REFUND),
+            Card::new(Value::Tri, Color::Blue),
+            Card::new(Value::Bi, Color::Green),
+        ])
+        .build()
+        .unwrap();
+    let outcome = game.perform(Action::Buy { cards: vec![0, 1, 2], target: ValueOrMonad::Value(Value::Quad) });
+    assert_eq!(outcome.ok().map(|outcome| outcome.cards), Some(2));
+    assert!(game.players[0].hand.last().is_some_and(|card| card.is_common()));
+
+    let mut game = GameBuilder::new(NumPlayers::Two)
+        .rules(mixing_in(&PEEK))
+        .hand(0, vec![special(Value::Bi, Color::Red, &PEEK), Card::new(Value::Bi, Color::Blue)])
+        .build()
+        .unwrap();
+    let top = game.table.deck(Value::Tri)[game.table.deck(Value::Tri).len() - 2].clone();
+    let outcome = game.perform(Action::Trade { card1: 0, card2: 1, bonus: false }).unwrap();
+    assert!(outcome.revealed == vec![top]);
+}
+
+#[test]
+fn errors_say_what_went_wrong() {
+    use std::error::Error;
+    use super::{
+        action::ActionError,
+        builder::GameBuilder,
+        card::{Color, Value},
+        BuyError,
+        TradeError,
+    };
+
+    let tri = card::SIX_COLORS.colors().filter(|&color| color != Color::Red).map(|color| Card::new(Value::Tri, color));
+    let mut game = GameBuilder::new(NumPlayers::Two)
+        .identity(0, Color::Green)
+        .hand(0, vec![
+            Card::new(Value::Tri, Color::Red),
+            Card::new(Value::Bi, Color::Orange),
+            Card::new(Value::Bi, Color::Blue),
+        ])
+        .hand(1, tri.collect())
+        .deck(Value::Tri, Vec::new())
+        .build()
+        .expect("consistent position");
+
+    let error = game.perform(Action::Buy { cards: vec![1, 2], target: ValueOrMonad::Value(Value::Quad) });
+    assert!(matches!(error, Err(ActionError::Buy(BuyError::NotEnoughPoints { price: 16, offered: 6 }))));
+    let error = game.perform(Action::Buy { cards: vec![2, 0], target: ValueOrMonad::Value(Value::Tri) });
+    assert!(matches!(error, Err(ActionError::Buy(BuyError::SameValue { card: 0, price: 7 }))));
+    assert!(matches!(game.trade(0, 1, 2, false), Err(TradeError::OutOfCards(value)) if value == Value::Tri));
+    assert_eq!(game.trade(0, 1, 2, false).unwrap_err().to_string(), "The Tri deck is out of cards!");
+    assert!(matches!(game.trade(0, 0, 1, false), Err(TradeError::SameTemperature(0, 1))));
+
+    let error = game.perform(Action::Leap { cards: vec![1, 1, 1, 1] }).err().expect("a repeated card");
+    assert_eq!(error.source().map(|source| source.to_string()), Some("Card 1 was selected more than once".to_string()));
+
+    // Names are looked up on the ladder and colour set in play, never on the first one that has them.
+    assert!(card::STANDARD.value("Sext").is_none());
+    assert!(card::SIX_TIERS.value("Quint").is_some_and(|quint| *quint.ladder() == card::SIX_TIERS));
+    assert!(card::SIX_COLORS.color("mauve").is_none());
+    assert!(card::THREE_GROUPS.color("Yellow").is_some_and(|yellow| *yellow.set() == card::THREE_GROUPS));
+    assert_eq!("Standard, turn limit soon".parse::<super::rules::Rules>().err().map(|error| error.to_string()),
+        Some("'turn limit soon' isn't a rule option".to_string()));
+}
+
+#[test]
+fn actions_on_a_broken_game_say_so_in_debug_builds() {
+    use super::{action::ActionError, card::Value, invariant::InvariantError};
+
+    // A card has gone missing from the Tri deck, so even a legal action leaves the game broken.
+    let mut game = Game::with_rng(NumPlayers::Two, &mut StdRng::seed_from_u64(0));
+    game.table.deck_mut(Value::Tri).remove(0);
+    let action = game.legal_actions()[0].clone();
+    let error = game.perform(action).err();
+    if cfg!(debug_assertions) {
+        assert!(matches!(error, Some(ActionError::Broken(InvariantError::CardCount { .. }))));
+    } else {
+        assert!(error.is_none());
+    }
+}
@@ -1,25 +1,380 @@
+mod bot;
 mod game;
+mod profile;
+mod puzzle;
+mod tournament;
 mod ui;
 
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
 use std::{
     env::args,
-    io::{stdin, stdout},
+    fmt,
+    fs,
+    io::{self, stdin, stdout},
+    path::{Path, PathBuf},
+    process::exit,
 };
 
 use crate::{
-    ui::Ui,
-    game::Game,
+    ui::{lang::Lang, style::Style, PromptError, Ui},
+    game::{
+        card::{ColorSet, Ladder, SIX_COLORS, STANDARD},
+        expansion::Special,
+        goal::Goal,
+        identity::Identities,
+        invariant::InvariantError,
+        record::Record,
+        Game,
+        NumPlayers,
+    },
+    profile::Profiles,
+    puzzle::Puzzle,
+    tournament::Options,
 };
 
+/// The game or puzzle ended without being won, there was no plan to win it, or the draft was cancelled.
+const UNFINISHED: i32 = 1;
+/// The command line, a position or a file given on it couldn't be read.
+const USAGE: i32 = 2;
+/// Reading or writing the terminal or a file failed.
+const IO: i32 = 3;
+/// The game reached a state the rules can't produce, as caught by `--paranoid`.
+const IMPOSSIBLE: i32 = 4;
+
+/// How a played game ends the process: cleanly once it's over, `UNFINISHED` if it was left part way.
+fn exit_code(game: &Game) -> i32 {
+    if game.is_over() { 0 } else { UNFINISHED }
+}
+
+/// Reports a problem with the command line and exits.
+fn usage(message: impl fmt::Display) -> ! {
+    eprintln!("{}", message);
+    exit(USAGE)
+}
+
+/// Reports an error from playing or from a file and exits, telling a broken game apart from a
+/// failed read or write.
+fn fail(context: &str, error: io::Error) -> ! {
+    eprintln!("{}: {}", context, error);
+    let impossible = error.get_ref().is_some_and(|inner| inner.is::<InvariantError>());
+    exit(if impossible { IMPOSSIBLE } else { IO })
+}
+
+/// The style asked for anywhere on the command line, so it applies to every subcommand.
+fn style() -> Style {
+    if args().any(|arg| arg == "--screen-reader") {
+        Style::ScreenReader
+    } else if args().any(|arg| arg == "--color-blind") {
+        Style::ColorBlind
+    } else {
+        Style::detect()
+    }
+}
+
+/// The language asked for with `--lang`, or else the locale's.
+fn lang() -> Lang {
+    match args().skip_while(|arg| arg != "--lang").nth(1) {
+        Some(code) => Lang::from_code(&code).unwrap_or_else(|| usage("--lang should be followed by en or es")),
+        None => Lang::detect(),
+    }
+}
+
 fn main() {
-    let num_players = args()
-        .skip(1)
-        .next()
-        .expect("First argument should be the number of players")
-        .parse()
-        .expect("Unable to parse number of players");
+    let mut args = args().skip(1);
+    let first = args.next().unwrap_or_else(|| {
+        usage("First argument should be the number of players, --position, \
+            'leaderboard', 'tournament', 'solve', 'puzzle' or 'tutorial'")
+    });
+
+    let path = Profiles::default_path();
+
+    if first == "leaderboard" {
+        let profiles = Profiles::load(&path).unwrap_or_else(|error| fail("Unable to read player profiles", error));
+        match args.next() {
+            Some(name) => match profiles.get(&name) {
+                Some(profile) => print!("{}", profile),
+                None => println!("No profile named {}", name),
+            },
+            None => print!("{}", profiles),
+        }
+        return;
+    }
+
+    if first == "tournament" {
+        let options = Options::parse(args).unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            eprintln!("Usage: tournament [--rounds N] [--seed N] [--max-turns N] [--out PATH] BOT BOT...");
+            eprintln!("Bots: random, greedy, ext:<command>");
+            exit(USAGE);
+        });
+        let (games, standings) = tournament::run(&options).unwrap_or_else(|error| fail("Tournament failed", error));
+        tournament::write_report(&options, games, &standings)
+            .unwrap_or_else(|error| fail("Unable to write tournament report", error));
+        for (rank, standing) in standings.iter().enumerate() {
+            println!("{}. {} ({:.0})", rank + 1, standing.name, standing.rating);
+        }
+        return;
+    }
+
+    if first == "solve" {
+        let position = args.next().unwrap_or_else(|| usage("solve should be followed by a position"));
+        let turns = match (args.next().as_deref(), args.next()) {
+            (Some("--turns"), Some(turns)) => {
+                turns.parse().unwrap_or_else(|_| usage("--turns should be followed by a number"))
+            },
+            (None, _) => 3,
+            _ => usage("Usage: solve POSITION [--turns N]"),
+        };
+        let game = Game::from_position(&position)
+            .unwrap_or_else(|error| usage(format!("Unable to read position: {}", error)));
+        match game.solve(&Goal::monad(turns)) {
+            Ok(plan) => print!("{}", plan),
+            Err(error) => {
+                println!("{}", error);
+                exit(UNFINISHED);
+            },
+        }
+        return;
+    }
+
+    if first == "tutorial" {
+        let (stdin, stdout) = (stdin(), stdout());
+        let mut ui = Ui {
+            input: stdin.lock(),
+            output: stdout.lock(),
+            paranoid: false,
+            record: None,
+            style: style(),
+            lang: lang(),
+        };
+        match ui.tutorial() {
+            Ok(finished) => println!("You finished {} lesson(s).", finished),
+            Err(error) => fail("The tutorial stopped", error),
+        }
+        return;
+    }
+
+    if first == "puzzle" {
+        let pack = Puzzle::starter_pack();
+        let puzzle = match args.next() {
+            Some(choice) => match choice.parse::<usize>() {
+                Ok(number) if (1..=pack.len()).contains(&number) => pack.into_iter().nth(number - 1).unwrap(),
+                _ => Puzzle::load(Path::new(&choice)).unwrap_or_else(|error| usage(error)),
+            },
+            None => {
+                for (i, puzzle) in pack.iter().enumerate() {
+                    println!("{}. {}: {}", i + 1, puzzle.name, puzzle.goal);
+                }
+                println!("Play one with: puzzle NUMBER, or puzzle FILE for your own");
+                return;
+            },
+        };
+
+        println!("{}", puzzle.name);
+        if !puzzle.description.is_empty() {
+            println!("{}", puzzle.description);
+        }
+        let (stdin, stdout) = (stdin(), stdout());
+        let mut ui = Ui {
+            input: stdin.lock(),
+            output: stdout.lock(),
+            paranoid: false,
+            record: None,
+            style: style(),
+            lang: lang(),
+        };
+        match ui.challenge(puzzle.game, &puzzle.goal) {
+            Ok(true) => {},
+            Ok(false) => exit(UNFINISHED),
+            Err(error) => fail("The puzzle stopped", error),
+        }
+        return;
+    }
+
+    let position = if first == "--position" {
+        Some(args.next().unwrap_or_else(|| usage("--position should be followed by a position")))
+    } else {
+        None
+    };
+
+    let mut paranoid = false;
+    let mut turn_limit: Option<usize> = None;
+    let mut shuffle_on_flip = false;
+    let mut auto_flip = false;
+    let mut identities: Option<Identities> = None;
+    let mut ladder: Option<&'static Ladder> = None;
+    let mut color_set: Option<&'static ColorSet> = None;
+    let mut teams: Option<usize> = None;
+    let mut expansion: Vec<&'static Special> = Vec::new();
+    let mut seed: Option<u64> = None;
+    let mut record_path: Option<PathBuf> = None;
+    // Naming every player opts the game into the profile history.
+    let mut names: Vec<String> = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--paranoid" => paranoid = true,
+            "--seed" => seed = Some(
+                args.next()
+                    .and_then(|seed| seed.parse().ok())
+                    .unwrap_or_else(|| usage("--seed should be followed by a number"))
+            ),
+            "--turn-limit" => turn_limit = Some(
+                args.next()
+                    .and_then(|limit| limit.parse().ok())
+                    .unwrap_or_else(|| usage("--turn-limit should be followed by a number"))
+            ),
+            "--shuffle-on-flip" => shuffle_on_flip = true,
+            "--screen-reader" | "--color-blind" => {},
+            "--lang" => drop(args.next()),
+            "--auto-flip" => auto_flip = true,
+            "--ladder" => ladder = Some(
+                args.next()
+                    .and_then(|name| Ladder::named(&name))
+                    .unwrap_or_else(|| usage("--ladder should be followed by standard or six"))
+            ),
+            "--colors" => color_set = Some(
+                args.next()
+                    .and_then(|name| ColorSet::named(&name))
+                    .unwrap_or_else(|| usage("--colors should be followed by standard, eight or three"))
+            ),
+            "--teams" => teams = Some(
+                args.next()
+                    .and_then(|limit| limit.parse().ok())
+                    .unwrap_or_else(|| usage("--teams should be followed by how many cards partners may pass a turn"))
+            ),
+            "--expansion" => expansion = args
+                .next()
+                .and_then(|names| names.split(',').map(Special::named).collect())
+                .unwrap_or_else(|| {
+                    usage("--expansion should be followed by a comma separated list of prism, refund and peek")
+                }),
+            "--identities" => identities = Some(
+                args.next()
+                    .map(|policy| policy.parse().unwrap_or_else(|error| usage(error)))
+                    .unwrap_or_else(|| usage("--identities should be followed by random, balanced, seats or draft"))
+            ),
+            "--record" => record_path = Some(
+                args.next().map(PathBuf::from).unwrap_or_else(|| usage("--record should be followed by a file name"))
+            ),
+            flag if flag.starts_with("--") => usage(format!("Unknown flag: {}", flag)),
+            _ if !profile::valid_name(&arg) => usage(format!("{:?} can't be a player name", arg)),
+            _ => names.push(arg),
+        }
+    }
+
+    if identities.is_some() && teams.is_some() {
+        usage("--identities can't be combined with --teams, which seats partners by bonus pair");
+    }
+
+    // Without --seed a seed is still picked and recorded, so every game can be dealt again.
+    let seed = seed.unwrap_or_else(|| thread_rng().gen());
+    let game = match position {
+        Some(_) if identities.is_some() || ladder.is_some() || color_set.is_some() || !expansion.is_empty() => {
+            usage("--identities, --ladder, --colors and --expansion only apply to a new game")
+        },
+        Some(_) if turn_limit.is_some() || shuffle_on_flip || auto_flip || teams.is_some() => {
+            usage("--turn-limit, --shuffle-on-flip, --auto-flip and --teams only apply to a new game, \
+                a position carries its own rules")
+        },
+        Some(position) => Game::from_position(&position)
+            .unwrap_or_else(|error| usage(format!("Unable to read position: {}", error))),
+        None => {
+            let num_players: NumPlayers = first.parse().unwrap_or_else(|error| usage(error));
+            let ladder = ladder.unwrap_or(&STANDARD);
+            let color_set = color_set.unwrap_or(&SIX_COLORS);
+            let mut rng = StdRng::seed_from_u64(seed);
+            let dealt = match teams {
+                Some(_) if !matches!(num_players, NumPlayers::Four) => usage("--teams needs 4 players"),
+                Some(limit) => Some(Game::with_teams(limit, ladder, color_set, &mut rng)),
+                None => Game::with_policy(num_players, identities.unwrap_or_default(), ladder, color_set, &mut rng),
+            };
+            let mut game = match dealt {
+                Some(game) => game,
+                None => {
+                    let (stdin, stdout) = (stdin(), stdout());
+                    let mut ui = Ui {
+                        input: stdin.lock(),
+                        output: stdout.lock(),
+                        paranoid,
+                        record: None,
+                        style: style(),
+                        lang: lang(),
+                    };
+                    let picks = match ui.draft(num_players, color_set) {
+                        Ok(picks) => picks,
+                        Err(PromptError::Io(error)) => fail("The draft stopped", error),
+                        Err(_) => {
+                            eprintln!("The draft was cancelled");
+                            exit(UNFINISHED);
+                        },
+                    };
+                    Game::with_identities(&picks, ladder, color_set, &mut rng)
+                        .expect("A draft hands out different colours")
+                },
+            };
+            if !expansion.is_empty() {
+                game.add_expansion(expansion, &mut rng);
+            }
+            game.rules.turn_limit = turn_limit;
+            game.rules.auto_flip = auto_flip;
+            game.rules.teams = teams;
+            if let Err(error) = game.check_teams() {
+                usage(format!("Unable to play in teams: {}", error));
+            }
+            if shuffle_on_flip {
+                game.rules.shuffle_seed = Some(seed);
+            }
+            game
+        },
+    };
+    let num_players = game.players.len();
+
+    if !names.is_empty() && names.len() != num_players {
+        usage(format!("Expected {} player names, got {}", num_players, names.len()));
+    }
+
+    let record = record_path.as_ref().map(|_| {
+        let mut record = Record::new(&game);
+        let players = game.players
+            .iter()
+            .enumerate()
+            .map(|(i, player)| match names.get(i) {
+                Some(name) => format!("{} ({})", name, player.identity),
+                None => format!("Player {} ({})", i + 1, player.identity),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        record.set_header("Players", &players);
+        record.set_header("Seed", &seed.to_string());
+        record.set_header("Rules", &game.rules.to_string());
+        record
+    });
 
     let (stdin, stdout) = (stdin(), stdout());
-    let ui = Ui { input: stdin.lock(), output: stdout.lock() };
-    ui.play(Game::new(num_players)).unwrap();
+    let mut ui = Ui {
+        input: stdin.lock(),
+        output: stdout.lock(),
+        paranoid,
+        record,
+        style: style(),
+        lang: lang(),
+    };
+    let result = ui.play(game);
+
+    // Write down whatever was played, even if the game was cut short.
+    if let (Some(path), Some(mut record)) = (record_path, ui.record.take()) {
+        if let Ok(game) = &result {
+            record.finish(game);
+        }
+        fs::write(&path, record.to_string()).unwrap_or_else(|error| fail("Unable to write the game record", error));
+    }
+
+    let game = result.unwrap_or_else(|error| fail("The game stopped", error));
+
+    if game.is_over() && !names.is_empty() {
+        let mut profiles = Profiles::load(&path).unwrap_or_else(|error| fail("Unable to read player profiles", error));
+        profiles.record(&names, &game);
+        profiles.save(&path).unwrap_or_else(|error| fail("Unable to save player profiles", error));
+    }
+    exit(exit_code(&game));
 }
@@ -0,0 +1,295 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt,
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use crate::game::Game;
+
+#[derive(Default)]
+pub struct Record {
+    pub wins: usize,
+    pub losses: usize,
+    pub draws: usize,
+}
+
+#[derive(Default)]
+pub struct Profile {
+    pub name: String,
+    pub games: usize,
+    pub wins: usize,
+    pub monads: usize,
+    pub identities: BTreeMap<String, usize>,
+    pub versus: BTreeMap<String, Record>,
+}
+
+impl Profile {
+    pub fn average_monads(&self) -> f64 {
+        if self.games == 0 { 0.0 } else { self.monads as f64 / self.games as f64 }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 { 0.0 } else { self.wins as f64 / self.games as f64 }
+    }
+
+    pub fn favourite_identity(&self) -> Option<&str> {
+        self.identities
+            .iter()
+            .max_by_key(|(_, &count)| count)
+            .map(|(color, _)| color.as_str())
+    }
+}
+
+/// Whether `name` can be kept in the profile file, which has no way of writing tabs or line breaks.
+pub fn valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.chars().any(char::is_control)
+}
+
+/// Player profiles, kept as a tab separated file with one line per profile or head-to-head record.
+#[derive(Default)]
+pub struct Profiles(BTreeMap<String, Profile>);
+
+impl Profiles {
+    /// `MONAD_PROFILES` if set, otherwise `.monad_profiles` in the home directory.
+    pub fn default_path() -> PathBuf {
+        if let Some(path) = env::var_os("MONAD_PROFILES") {
+            return PathBuf::from(path);
+        }
+        env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_default()
+            .join(".monad_profiles")
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(ref error) if error.kind() == ErrorKind::NotFound => return Ok(Profiles::default()),
+            Err(error) => return Err(error),
+        };
+
+        let mut profiles = Profiles::default();
+        for (number, line) in contents.lines().enumerate() {
+            if profiles.parse_line(line).is_none() {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{}:{}: malformed profile line", path.display(), number + 1),
+                ));
+            }
+        }
+
+        Ok(profiles)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut contents = String::new();
+        for profile in self.0.values() {
+            let identities = profile.identities
+                .iter()
+                .map(|(color, count)| format!("{}:{}", color, count))
+                .collect::<Vec<_>>()
+                .join(",");
+            contents += &format!(
+                "profile\t{}\t{}\t{}\t{}\t{}\n",
+                profile.name, profile.games, profile.wins, profile.monads, identities,
+            );
+            for (opponent, record) in &profile.versus {
+                contents += &format!(
+                    "versus\t{}\t{}\t{}\t{}\t{}\n",
+                    profile.name, opponent, record.wins, record.losses, record.draws,
+                );
+            }
+        }
+
+        fs::write(path, contents)
+    }
+
+    fn parse_line(&mut self, line: &str) -> Option<()> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields.as_slice() {
+            ["profile", name, games, wins, monads, identities] => {
+                let profile = self.profile_mut(name);
+                profile.games = games.parse().ok()?;
+                profile.wins = wins.parse().ok()?;
+                profile.monads = monads.parse().ok()?;
+                for entry in identities.split(',').filter(|entry| !entry.is_empty()) {
+                    let mut parts = entry.splitn(2, ':');
+                    let color = parts.next()?;
+                    let count = parts.next()?.parse().ok()?;
+                    profile.identities.insert(color.to_string(), count);
+                }
+            },
+            ["versus", name, opponent, wins, losses, draws] => {
+                let record = Record {
+                    wins: wins.parse().ok()?,
+                    losses: losses.parse().ok()?,
+                    draws: draws.parse().ok()?,
+                };
+                self.profile_mut(name).versus.insert(opponent.to_string(), record);
+            },
+            [""] => {},
+            _ => return None,
+        }
+
+        Some(())
+    }
+
+    fn profile_mut(&mut self, name: &str) -> &mut Profile {
+        self.0
+            .entry(name.to_string())
+            .or_insert_with(|| Profile { name: name.to_string(), ..Profile::default() })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.0.get(name)
+    }
+
+    /// Adds a finished game to the profiles. `names[i]` is the name of player `i`.
+    pub fn record(&mut self, names: &[String], game: &Game) {
+        let winners = game.winners();
+
+        for (i, name) in names.iter().enumerate() {
+            let player = &game.players[i];
+            let profile = self.profile_mut(name);
+            profile.games += 1;
+            profile.monads += player.monads.len();
+            if winners.contains(&i) {
+                profile.wins += 1;
+            }
+            *profile.identities.entry(player.identity.to_string()).or_insert(0) += 1;
+
+            // Partners share a score, so they are never each other's opponents.
+            let opponents = names.iter().enumerate().filter(|&(j, _)| j != i && game.partner(i) != Some(j));
+            for (j, opponent) in opponents {
+                let ours = game.score(i);
+                let theirs = game.score(j);
+                let record = profile.versus.entry(opponent.clone()).or_default();
+                if ours > theirs {
+                    record.wins += 1;
+                } else if ours < theirs {
+                    record.losses += 1;
+                } else {
+                    record.draws += 1;
+                }
+            }
+        }
+    }
+
+    /// Profiles ordered by wins, then win rate, then average monads.
+    pub fn leaderboard(&self) -> Vec<&Profile> {
+        let mut ranking: Vec<&Profile> = self.0.values().collect();
+        ranking.sort_by(|a, b|
+            b.wins.cmp(&a.wins)
+                .then(b.win_rate().partial_cmp(&a.win_rate()).unwrap())
+                .then(b.average_monads().partial_cmp(&a.average_monads()).unwrap())
+        );
+        ranking
+    }
+}
+
+impl fmt::Display for Profiles {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(fmt, "{:<5}{:<16}{:>6}{:>6}{:>7}{:>8}  Favourite", "Rank", "Name", "Games", "Wins", "Win%", "Monads")?;
+        for (rank, profile) in self.leaderboard().into_iter().enumerate() {
+            writeln!(
+                fmt,
+                "{:<5}{:<16}{:>6}{:>6}{:>6.0}%{:>8.1}  {}",
+                rank + 1,
+                profile.name,
+                profile.games,
+                profile.wins,
+                profile.win_rate() * 100.0,
+                profile.average_monads(),
+                profile.favourite_identity().unwrap_or("-"),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            fmt,
+            "{}: {} game(s), {} win(s), {:.1} monads on average, favourite identity {}",
+            self.name,
+            self.games,
+            self.wins,
+            self.average_monads(),
+            self.favourite_identity().unwrap_or("-"),
+        )?;
+        for (opponent, record) in &self.versus {
+            writeln!(
+                fmt,
+                "  vs {:<16}{} won, {} lost, {} drawn",
+                opponent, record.wins, record.losses, record.draws,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::env;
+
+    use super::*;
+    use crate::game::{card::{self, Monad}, NumPlayers};
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn profiles_round_trip_and_rank_by_wins() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut profiles = Profiles::default();
+
+        let mut game = Game::with_rng(NumPlayers::Two, &mut rng);
+        game.players[1].monads = vec![Monad; 2];
+        profiles.record(&names(&["ann", "bo"]), &game);
+        let mut game = Game::with_rng(NumPlayers::Three, &mut rng);
+        game.players[1].monads = vec![Monad; 1];
+        game.players[2].monads = vec![Monad; 3];
+        profiles.record(&names(&["ann", "bo", "cy"]), &game);
+
+        let ranking = |profiles: &Profiles| -> Vec<String> {
+            profiles.leaderboard().iter().map(|profile| profile.name.clone()).collect()
+        };
+        // Bo and Cy both have a win, but Cy has the better win rate.
+        assert_eq!(ranking(&profiles), names(&["cy", "bo", "ann"]));
+
+        let path = env::temp_dir().join("monad_profiles_round_trip");
+        profiles.save(&path).expect("writable profiles");
+        let loaded = Profiles::load(&path).expect("readable profiles");
+        assert_eq!(ranking(&loaded), ranking(&profiles));
+        let bo = loaded.get("bo").expect("bo's profile");
+        assert_eq!((bo.games, bo.wins, bo.monads), (2, 1, 3));
+        assert_eq!((bo.versus["ann"].wins, bo.versus["cy"].losses), (2, 1));
+        assert_eq!(loaded.to_string(), profiles.to_string());
+    }
+
+    #[test]
+    fn partners_are_not_opponents_and_names_stay_on_one_line() {
+        let mut game = Game::with_teams(1, &card::STANDARD, &card::SIX_COLORS, &mut StdRng::seed_from_u64(0));
+        game.players[0].monads = vec![Monad; 1];
+        let mut profiles = Profiles::default();
+        profiles.record(&names(&["ann", "bo", "cy", "di"]), &game);
+
+        let ann = profiles.get("ann").expect("ann's profile");
+        assert!(!ann.versus.contains_key("cy"));
+        assert_eq!((ann.versus["bo"].wins, ann.versus["di"].wins), (1, 1));
+        assert_eq!(profiles.get("cy").expect("cy's profile").versus["bo"].wins, 1);
+
+        assert!(valid_name("Ann Lee"));
+        assert!(!valid_name("ann\tbo"));
+        assert!(!valid_name("ann\n"));
+        assert!(!valid_name(""));
+    }
+}
@@ -0,0 +1,115 @@
+use std::{error, fmt, fs, io, path::Path, str::FromStr};
+
+use crate::game::{
+    card::ValueOrMonad,
+    goal::Goal,
+    position::PositionError,
+    record::{Record, RecordError},
+    Game,
+};
+
+/// The puzzles that come with the game, easiest first.
+const STARTER_PACK: [&str; 5] = [
+    include_str!("../puzzles/temperature.txt"),
+    include_str!("../puzzles/wilds.txt"),
+    include_str!("../puzzles/leap.txt"),
+    include_str!("../puzzles/bonus.txt"),
+    include_str!("../puzzles/two-turns.txt"),
+];
+
+#[derive(Debug)]
+pub enum PuzzleError {
+    Io(io::Error),
+    Record(RecordError),
+    MissingHeader(&'static str),
+    BadHeader(&'static str, String),
+    Position(PositionError),
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::PuzzleError::*;
+        match self {
+            Io(error) => write!(fmt, "Unable to read the puzzle: {}", error),
+            Record(error) => write!(fmt, "{}", error),
+            MissingHeader(name) => write!(fmt, "The puzzle has no {} header", name),
+            BadHeader(name, value) => write!(fmt, "Not a valid {}: {}", name, value),
+            Position(error) => write!(fmt, "Bad starting position: {}", error),
+        }
+    }
+}
+
+impl error::Error for PuzzleError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            PuzzleError::Io(error) => Some(error),
+            PuzzleError::Record(error) => Some(error),
+            PuzzleError::Position(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// A position with a goal, written as record headers:
+///
+/// ```text
+/// [Name "Temperature"]
+/// [Description "Only cards of different temperatures can be traded."]
+/// [Position "..."]
+/// [Goal "Bi"]
+/// [Turns "1"]
+/// [Draws "no"]
+/// ```
+///
+/// `Goal` is a tier or `Monad`. `Turns` defaults to 1 and `Draws` to yes.
+pub struct Puzzle {
+    pub name: String,
+    pub description: String,
+    pub game: Game,
+    pub goal: Goal,
+}
+
+impl Puzzle {
+    pub fn load(path: &Path) -> Result<Self, PuzzleError> {
+        fs::read_to_string(path).map_err(PuzzleError::Io)?.parse()
+    }
+
+    pub fn starter_pack() -> Vec<Puzzle> {
+        STARTER_PACK
+            .iter()
+            .map(|source| source.parse().expect("The starter puzzles should be valid"))
+            .collect()
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = PuzzleError;
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let record: Record = source.parse().map_err(PuzzleError::Record)?;
+        let header = |name: &'static str| record.header(name).ok_or(PuzzleError::MissingHeader(name));
+        let bad = |name: &'static str, value: &str| PuzzleError::BadHeader(name, value.to_string());
+
+        // The goal is named on the ladder the position is played on.
+        let game = Game::from_position(header("Position")?).map_err(PuzzleError::Position)?;
+        let target = match header("Goal")? {
+            "Monad" => ValueOrMonad::Monad,
+            value => ValueOrMonad::Value(game.table.ladder.value(value).ok_or_else(|| bad("Goal", value))?),
+        };
+        let turns = match record.header("Turns") {
+            Some(turns) => turns.parse().ok().filter(|&turns| turns > 0).ok_or_else(|| bad("Turns", turns))?,
+            None => 1,
+        };
+        let draws = match record.header("Draws") {
+            Some("yes") | None => true,
+            Some("no") => false,
+            Some(draws) => return Err(bad("Draws", draws)),
+        };
+
+        Ok(Puzzle {
+            name: header("Name")?.to_string(),
+            description: record.header("Description").unwrap_or("").to_string(),
+            game,
+            goal: Goal { target, turns, draws },
+        })
+    }
+}
@@ -0,0 +1,228 @@
+use itertools::Itertools;
+use rand::{rngs::StdRng, SeedableRng};
+use std::{
+    fs,
+    io,
+    path::PathBuf,
+};
+
+use crate::{
+    bot::{self, BotSpec, Strategy},
+    game::{Game, NumPlayers},
+};
+
+const INITIAL_RATING: f64 = 1500.0;
+const K_FACTOR: f64 = 32.0;
+
+pub struct Options {
+    pub bots: Vec<BotSpec>,
+    /// How many times every table is played from every seat rotation.
+    pub rounds: usize,
+    pub seed: u64,
+    pub max_turns: usize,
+    /// The report is written to this path with `.csv` and `.md` extensions.
+    pub out: PathBuf,
+}
+
+impl Options {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options {
+            bots: Vec::new(),
+            rounds: 1,
+            seed: 0,
+            max_turns: 1000,
+            out: PathBuf::from("tournament"),
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| args.next().ok_or(format!("{} needs a value", name));
+            match arg.as_str() {
+                "--rounds" => options.rounds = value(&arg)?.parse().map_err(|_| "Invalid --rounds")?,
+                "--seed" => options.seed = value(&arg)?.parse().map_err(|_| "Invalid --seed")?,
+                "--max-turns" => options.max_turns = value(&arg)?.parse().map_err(|_| "Invalid --max-turns")?,
+                "--out" => options.out = PathBuf::from(value(&arg)?),
+                bot => options.bots.push(bot.parse::<BotSpec>().map_err(|error| error.to_string())?),
+            }
+        }
+
+        if options.bots.len() < 2 {
+            return Err("A tournament needs at least two bots".to_string());
+        }
+
+        Ok(options)
+    }
+}
+
+pub struct Standing {
+    pub name: String,
+    pub rating: f64,
+    pub games: usize,
+    pub wins: usize,
+    pub monads: usize,
+}
+
+/// The tables of `size` seats to play: every combination of entrants, or when there are fewer
+/// entrants than seats, all of them with the first ones sitting down again to fill the table.
+fn tables(entrants: usize, size: usize) -> Vec<Vec<usize>> {
+    if entrants >= size {
+        (0..entrants).combinations(size).collect()
+    } else {
+        vec![(0..entrants).cycle().take(size).collect()]
+    }
+}
+
+/// Plays every combination of bots at 2, 3 and 4 player tables, rotating seats and seeds.
+pub fn run(options: &Options) -> io::Result<(usize, Vec<Standing>)> {
+    let mut standings: Vec<Standing> = options.bots
+        .iter()
+        .map(|spec| Standing { name: spec.to_string(), rating: INITIAL_RATING, games: 0, wins: 0, monads: 0 })
+        .collect();
+
+    let mut seed = options.seed;
+    let mut games = 0;
+
+    for &num_players in &[NumPlayers::Two, NumPlayers::Three, NumPlayers::Four] {
+        let size = num_players as usize;
+        for table in tables(options.bots.len(), size) {
+            for _ in 0..options.rounds {
+                for rotation in 0..size {
+                    let mut seats = table.clone();
+                    seats.rotate_left(rotation);
+
+                    // Each seat gets a seed of its own, so two copies of a random bot don't play alike.
+                    let mut bots = seats
+                        .iter()
+                        .enumerate()
+                        .map(|(seat, &entrant)| options.bots[entrant].build(seed ^ ((seat as u64 + 1) << 32)))
+                        .collect::<io::Result<Vec<Box<dyn Strategy>>>>()?;
+
+                    let mut game = Game::with_rng(num_players, &mut StdRng::seed_from_u64(seed));
+                    game.rules.turn_limit = Some(options.max_turns);
+                    let game = bot::play(game, &mut bots)?;
+                    record(&mut standings, &seats, &game);
+
+                    seed += 1;
+                    games += 1;
+                }
+            }
+        }
+    }
+
+    standings.sort_by(|a, b| b.rating.partial_cmp(&a.rating).unwrap());
+
+    Ok((games, standings))
+}
+
+/// Updates ratings as if every pair of seats had played a game of Elo against each other. An entrant
+/// sitting at the table twice doesn't play itself.
+fn record(standings: &mut [Standing], seats: &[usize], game: &Game) {
+    let winners = game.winners();
+    let monads: Vec<usize> = game.players.iter().map(|player| player.monads.len()).collect();
+    let k = K_FACTOR / (seats.len() - 1) as f64;
+
+    let mut deltas = vec![0.0; seats.len()];
+    for (a, b) in (0..seats.len()).tuple_combinations().filter(|&(a, b)| seats[a] != seats[b]) {
+        let (rating_a, rating_b) = (standings[seats[a]].rating, standings[seats[b]].rating);
+        let expected = 1.0 / (1.0 + 10f64.powf((rating_b - rating_a) / 400.0));
+        let score = match monads[a].cmp(&monads[b]) {
+            std::cmp::Ordering::Greater => 1.0,
+            std::cmp::Ordering::Equal => 0.5,
+            std::cmp::Ordering::Less => 0.0,
+        };
+        deltas[a] += k * (score - expected);
+        deltas[b] -= k * (score - expected);
+    }
+
+    for (seat, &entrant) in seats.iter().enumerate() {
+        let standing = &mut standings[entrant];
+        standing.rating += deltas[seat];
+        standing.games += 1;
+        standing.monads += monads[seat];
+        if winners.contains(&seat) {
+            standing.wins += 1;
+        }
+    }
+}
+
+fn row(standing: &Standing) -> (f64, f64) {
+    let games = standing.games.max(1) as f64;
+    (standing.wins as f64 / games * 100.0, standing.monads as f64 / games)
+}
+
+pub fn write_report(options: &Options, games: usize, standings: &[Standing]) -> io::Result<()> {
+    let mut csv = String::from("rank,bot,rating,games,wins,win_percent,average_monads\n");
+    let mut markdown = format!(
+        "# Tournament report\n\n\
+        {} games, {} round(s) per table and rotation, seeds {} to {}, at most {} turns per game.\n\n\
+        | Rank | Bot | Rating | Games | Wins | Win % | Avg monads |\n\
+        |-----:|-----|-------:|------:|-----:|------:|-----------:|\n",
+        games,
+        options.rounds,
+        options.seed,
+        options.seed + games.saturating_sub(1) as u64,
+        options.max_turns,
+    );
+
+    for (rank, standing) in standings.iter().enumerate() {
+        let (win_percent, average_monads) = row(standing);
+        csv += &format!(
+            "{},\"{}\",{:.1},{},{},{:.1},{:.2}\n",
+            rank + 1, standing.name.replace('"', "\"\""), standing.rating,
+            standing.games, standing.wins, win_percent, average_monads,
+        );
+        markdown += &format!(
+            "| {} | `{}` | {:.0} | {} | {} | {:.1} | {:.2} |\n",
+            rank + 1, standing.name, standing.rating,
+            standing.games, standing.wins, win_percent, average_monads,
+        );
+    }
+
+    fs::write(options.out.with_extension("csv"), csv)?;
+    fs::write(options.out.with_extension("md"), markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+    use crate::game::builder::GameBuilder;
+
+    #[test]
+    fn tables_fill_every_seat() {
+        assert_eq!(tables(3, 2), vec![vec![0, 1], vec![0, 2], vec![1, 2]]);
+        assert_eq!(tables(2, 3), vec![vec![0, 1, 0]]);
+        assert_eq!(tables(3, 4), vec![vec![0, 1, 2, 0]]);
+    }
+
+    #[test]
+    fn ratings_follow_elo_and_reach_the_report() {
+        let options = Options::parse(
+            ["greedy", "random", "--out"].iter().map(|arg| arg.to_string())
+                .chain(Some(env::temp_dir().join("monad_tournament").to_string_lossy().into_owned())),
+        )
+        .expect("valid options");
+        let mut standings: Vec<Standing> = options.bots
+            .iter()
+            .map(|spec| Standing { name: spec.to_string(), rating: INITIAL_RATING, games: 0, wins: 0, monads: 0 })
+            .collect();
+
+        // Evenly rated, so the winner takes half of K from the loser.
+        let game = GameBuilder::new(NumPlayers::Two).monads(0, 2).build().expect("consistent position");
+        record(&mut standings, &[0, 1], &game);
+        assert_eq!((standings[0].rating, standings[1].rating), (INITIAL_RATING + 16.0, INITIAL_RATING - 16.0));
+        assert_eq!((standings[0].wins, standings[0].monads, standings[1].games), (1, 2, 1));
+
+        // The two copies of random don't rate against each other, so the ratings still add up.
+        let game = GameBuilder::new(NumPlayers::Three).monads(0, 1).build().expect("consistent position");
+        record(&mut standings, &[1, 0, 1], &game);
+        assert!(standings[1].rating > INITIAL_RATING - 16.0);
+        assert_eq!(standings[0].rating + standings[1].rating, INITIAL_RATING * 2.0);
+
+        write_report(&options, 2, &standings).expect("writable report");
+        let markdown = fs::read_to_string(options.out.with_extension("md")).expect("markdown report");
+        let csv = fs::read_to_string(options.out.with_extension("csv")).expect("csv report");
+        assert!(markdown.contains("| 1 | `greedy` | 1507 | 2 | 1 | 50.0 | 1.00 |"));
+        assert!(csv.contains("2,\"random\","));
+    }
+}
@@ -0,0 +1,433 @@
+use itertools::Itertools;
+use std::io::{self, BufRead, Write};
+
+use super::{
+    lang::{Lang, Message, Text},
+    Ui,
+};
+use crate::game::{
+    card::{Card, Value},
+    expansion::{REFUND, SPECIALS},
+    table::MONADS,
+    Game,
+};
+
+struct Command {
+    name: &'static str,
+    usage: Text,
+    summary: Text,
+}
+
+const COMMANDS: [Command; 11] = [
+    Command {
+        name: "show",
+        usage: ["show", "show"],
+        summary: ["Show your hand and the table.", "Muestra tu mano y la mesa."],
+    },
+    Command {
+        name: "position",
+        usage: ["position", "position"],
+        summary: ["Print the game as a one-line position.", "Escribe la partida como una posición de una línea."],
+    },
+    Command {
+        name: "draw",
+        usage: ["draw", "draw"],
+        summary: ["Draw a common. Ends your turn.", "Roba una común. Termina tu turno."],
+    },
+    Command {
+        name: "flip",
+        usage: ["flip", "flip"],
+        summary: [
+            "Turn the discard pile into a new common deck once the common deck is empty. Ends your turn.",
+            "Da la vuelta a la pila de descarte como nuevo mazo común cuando este se acaba. Termina tu turno.",
+        ],
+    },
+    Command {
+        name: "trade",
+        usage: ["trade", "trade"],
+        summary: [
+            "Trade two cards for one of the next value up. See 'rules trading'.",
+            "Intercambia dos cartas por una del valor siguiente. Mira 'rules trading'.",
+        ],
+    },
+    Command {
+        name: "buy",
+        usage: ["buy, or buy auto <deck> [keep|spend]", "buy, o buy auto <mazo> [keep|spend]"],
+        summary: [
+            "Pay for a card or a monad with points; 'auto' picks the cheapest payment, keeping wilds and \
+            bonus pairs unless told to spend them. See 'rules buying'.",
+            "Paga una carta o una mónada con puntos; 'auto' elige el pago más barato, guardando comodines y \
+            parejas de bonificación salvo con 'spend'. Mira 'rules buying'.",
+        ],
+    },
+    Command {
+        name: "leap",
+        usage: ["leap", "leap"],
+        summary: [
+            "Turn commons into a higher card. See 'rules leaping'.",
+            "Convierte comunes en una carta más alta. Mira 'rules leaping'.",
+        ],
+    },
+    Command {
+        name: "pass",
+        usage: ["pass", "pass"],
+        summary: [
+            "Hand a card to your partner. See 'rules teams'.",
+            "Pasa una carta a tu compañero. Mira 'rules teams'.",
+        ],
+    },
+    Command {
+        name: "end",
+        usage: ["end", "end"],
+        summary: ["End your turn. See 'rules turns'.", "Termina tu turno. Mira 'rules turns'."],
+    },
+    Command {
+        name: "help",
+        usage: ["help [command]", "help [orden]"],
+        summary: ["List the commands, or explain one.", "Enumera las órdenes, o explica una."],
+    },
+    Command {
+        name: "rules",
+        usage: ["rules [topic]", "rules [tema]"],
+        summary: ["Explain the rules, or just one topic.", "Explica las reglas, o solo un tema."],
+    },
+];
+
+const TOPICS: [&str; 11] = [
+    "values", "temperature", "trading", "wilds", "bonus", "leaping", "buying", "monads", "turns", "teams", "expansion",
+];
+
+/// The command names, for prompts.
+pub fn command_names() -> String {
+    COMMANDS.iter().map(|command| command.name).join(" ")
+}
+
+fn capitalized(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+fn trade_result(value: Value, lang: Lang) -> String {
+    match value.succ() {
+        Some(next) => format!("{} {:?}", lang.pick(&["a", "un"]), next),
+        None => lang.pick(&["a monad", "una mónada"]).to_string(),
+    }
+}
+
+/// The values of `player`'s colour that are wild for them.
+fn wilds(game: &Game, player: usize) -> String {
+    let player = &game.players[player];
+    game.table.ladder
+        .values()
+        .filter(|&value| player.is_player_wild(&Card::new(value, player.identity)))
+        .map(|value| format!("{:?}", value))
+        .join(", ")
+}
+
+/// Whether the game is played with Refund cards, which give change.
+fn refunds(game: &Game) -> bool {
+    game.rules.expansion.contains(&&REFUND)
+}
+
+/// Explains `topic` for `player` in `lang`, from the rules the engine actually applies.
+fn rule(topic: &str, game: &Game, player: usize, lang: Lang) -> Option<String> {
+    match lang {
+        Lang::English => english_rule(topic, game, player),
+        Lang::Spanish => spanish_rule(topic, game, player),
+    }
+}
+
+fn english_rule(topic: &str, game: &Game, player: usize) -> Option<String> {
+    let identity = game.players[player].identity;
+    let ladder = game.table.ladder;
+    let colors = game.table.colors;
+    let monad_price = ladder.monad_price;
+
+    let text = match topic {
+        "values" => format!(
+            "Card values:\n{}\n",
+            ladder
+                .values()
+                .map(|value| format!(
+                    "  {:?}: {} point(s), {} of each colour",
+                    value,
+                    value.points(),
+                    value.tier().copies.unwrap_or(game.players.len()),
+                ))
+                .join("\n"),
+        ),
+        "temperature" => format!(
+            "{}\nOnly cards of different temperatures can be traded together.\n",
+            colors
+                .temps()
+                .map(|temp| {
+                    let group = capitalized(colors.groups[temp.0]);
+                    format!("{} colours: {}", group, colors.with_temp(temp).iter().join(", "))
+                })
+                .join("\n"),
+        ),
+        "trading" => format!(
+            "Trade two cards of the same value and different temperatures for the top card of the next deck up:\n{}\n",
+            ladder
+                .values()
+                .map(|value| format!("  two {:?} -> {}", value, trade_result(value, Lang::English)))
+                .join("\n"),
+        ),
+        "wilds" => format!(
+            "Your colour is {}. These {} cards are wild: {}.\n\
+            A wild traded with a card of another temperature counts as that card's value.\n",
+            identity,
+            identity,
+            wilds(game, player),
+        ),
+        "bonus" => format!(
+            "Bonus pairs: {}\n\
+            Trading a bonus pair can also draw one card of every lower value, once a turn. Wilds don't count.\n",
+            colors
+                .colors()
+                .filter(|color| color.index() < color.bonus_partner().index())
+                .map(|color| format!("{} and {}", color, color.bonus_partner()))
+                .join(", "),
+        ),
+        "leaping" => format!(
+            "Commons can leap straight to a higher card, whatever their colours:\n{}\n",
+            ladder
+                .leaps()
+                .filter_map(|count| ladder.leap(count).map(|value| format!("  {} commons -> a {:?}", count, value)))
+                .join("\n"),
+        ),
+        "buying" => format!(
+            "Pay with cards worth at least the price. Each card paid with must be worth less than what it buys,\n\
+            and {}.\n{}\n  Monad: {} points\n",
+            if refunds(game) {
+                "no change is given, except that a Refund card hands back what was overpaid as commons"
+            } else {
+                "no change is given"
+            },
+            ladder.values().skip(1).map(|value| format!("  {:?}: {} points", value, value.points())).join("\n"),
+            monad_price,
+        ),
+        "monads" => format!(
+            "A monad costs {} points, or two {:?}s traded together.\n\
+            There are {} monads and {} are left. The game ends when they are gone; the most monads wins.\n",
+            monad_price,
+            ladder.top(),
+            MONADS,
+            game.table.monad.len(),
+        ),
+        "turns" => {
+            let mut text = "On your turn, either draw or flip, which ends it, or trade, buy and leap as much as you like\n\
+                and then end it. You can only end a turn without doing anything when nothing else is possible.\n\
+                The discard pile can only be flipped once the common deck is empty.\n"
+                .to_string();
+            if game.rules.auto_flip {
+                text += "Drawing from an empty common deck flips the discard pile first.\n";
+            }
+            if game.rules.shuffle_seed.is_some() {
+                text += "The discard pile is shuffled as it is flipped.\n";
+            }
+            if let Some(limit) = game.rules.turn_limit {
+                text += &format!("The game ends after {} turns in all.\n", limit);
+            }
+            text
+        },
+        "teams" => match game.partner(player) {
+            Some(partner) => format!(
+                "You play with player {} ({}), sitting opposite you. You may pass them up to {} card(s) a turn.\n\
+                A pass is an action like a trade, buy or leap: once you have passed you can't draw or flip,\n\
+                but you can keep going or end your turn. Your monads are added together, and the side with\n\
+                the most wins.\n",
+                partner + 1,
+                game.players[partner].identity,
+                game.rules.teams.unwrap_or(0),
+            ),
+            None => "This game isn't played in teams. Four players can play as two teams of two, partners\n\
+                holding the colours of a bonus pair and passing each other cards.\n"
+                .to_string(),
+        },
+        "expansion" if game.rules.expansion.is_empty() => {
+            let kinds = SPECIALS.iter().map(|special| special.name).join(", ");
+            format!("This game has no expansion cards. The kinds there are: {}.\n", kinds)
+        },
+        "expansion" => game.rules.expansion
+            .iter()
+            .filter_map(|special| {
+                let value = ladder.values().nth(special.rank)?;
+                Some(format!("{} ({:?}, one of each colour): {}\n", special.name, value, special.description))
+            })
+            .collect(),
+        _ => return None,
+    };
+
+    Some(text)
+}
+
+fn spanish_rule(topic: &str, game: &Game, player: usize) -> Option<String> {
+    let identity = game.players[player].identity;
+    let ladder = game.table.ladder;
+    let colors = game.table.colors;
+    let monad_price = ladder.monad_price;
+
+    let text = match topic {
+        "values" => format!(
+            "Valores de las cartas:\n{}\n",
+            ladder
+                .values()
+                .map(|value| format!(
+                    "  {:?}: {} punto(s), {} de cada color",
+                    value,
+                    value.points(),
+                    value.tier().copies.unwrap_or(game.players.len()),
+                ))
+                .join("\n"),
+        ),
+        "temperature" => format!(
+            "{}\nSolo se pueden intercambiar juntas cartas de temperaturas distintas.\n",
+            colors
+                .temps()
+                .map(|temp| {
+                    let group = Lang::Spanish.group(colors.groups[temp.0]);
+                    format!("Colores del grupo {}: {}", group, colors.with_temp(temp).iter().join(", "))
+                })
+                .join("\n"),
+        ),
+        "trading" => format!(
+            "Intercambia dos cartas del mismo valor y temperaturas distintas por la carta de arriba del mazo \
+            siguiente:\n{}\n",
+            ladder
+                .values()
+                .map(|value| format!("  dos {:?} -> {}", value, trade_result(value, Lang::Spanish)))
+                .join("\n"),
+        ),
+        "wilds" => format!(
+            "Tu color es {}. Estas cartas {} son comodines: {}.\n\
+            Un comodín intercambiado con una carta de otra temperatura cuenta como el valor de esa carta.\n",
+            identity,
+            identity,
+            wilds(game, player),
+        ),
+        "bonus" => format!(
+            "Parejas de bonificación: {}\n\
+            Intercambiar una pareja de bonificación también roba una carta de cada valor inferior, una vez por \
+            turno. Los comodines no cuentan.\n",
+            colors
+                .colors()
+                .filter(|color| color.index() < color.bonus_partner().index())
+                .map(|color| format!("{} y {}", color, color.bonus_partner()))
+                .join(", "),
+        ),
+        "leaping" => format!(
+            "Las comunes pueden saltar directamente a una carta más alta, sean del color que sean:\n{}\n",
+            ladder
+                .leaps()
+                .filter_map(|count| ladder.leap(count).map(|value| format!("  {} comunes -> un {:?}", count, value)))
+                .join("\n"),
+        ),
+        "buying" => format!(
+            "Paga con cartas que sumen al menos el precio. Cada carta con la que pagas debe valer menos que lo que\n\
+            compra, y {}.\n{}\n  Monad: {} puntos\n",
+            if refunds(game) {
+                "no se da cambio, salvo que una carta Refund devuelve en comunes lo que se pagó de más"
+            } else {
+                "no se da cambio"
+            },
+            ladder.values().skip(1).map(|value| format!("  {:?}: {} puntos", value, value.points())).join("\n"),
+            monad_price,
+        ),
+        "monads" => format!(
+            "Una mónada cuesta {} puntos, o dos {:?} intercambiados juntos.\n\
+            Hay {} mónadas y quedan {}. La partida termina cuando se acaban; gana quien tenga más mónadas.\n",
+            monad_price,
+            ladder.top(),
+            MONADS,
+            game.table.monad.len(),
+        ),
+        "turns" => {
+            let mut text = "En tu turno, o robas o das la vuelta al descarte, y el turno termina, o intercambias,\n\
+                compras y saltas cuanto quieras y luego lo terminas. Solo puedes terminar un turno sin hacer nada\n\
+                cuando no hay nada más posible. El descarte solo se puede voltear cuando el mazo común está vacío.\n"
+                .to_string();
+            if game.rules.auto_flip {
+                text += "Robar de un mazo común vacío le da la vuelta antes a la pila de descarte.\n";
+            }
+            if game.rules.shuffle_seed.is_some() {
+                text += "La pila de descarte se baraja al darle la vuelta.\n";
+            }
+            if let Some(limit) = game.rules.turn_limit {
+                text += &format!("La partida termina tras {} turnos en total.\n", limit);
+            }
+            text
+        },
+        "teams" => match game.partner(player) {
+            Some(partner) => format!(
+                "Juegas con el jugador {} ({}), sentado enfrente. Puedes pasarle hasta {} carta(s) por turno.\n\
+                Pasar es una acción como intercambiar, comprar o saltar: después de pasar no puedes robar ni dar la\n\
+                vuelta al descarte, pero puedes seguir o terminar el turno. Vuestras mónadas se suman, y gana\n\
+                el equipo que tenga más.\n",
+                partner + 1,
+                game.players[partner].identity,
+                game.rules.teams.unwrap_or(0),
+            ),
+            None => "Esta partida no se juega por equipos. Cuatro jugadores pueden jugar en dos equipos de dos,\n\
+                con los compañeros llevando los colores de una pareja de bonificación y pasándose cartas.\n"
+                .to_string(),
+        },
+        "expansion" if game.rules.expansion.is_empty() => {
+            let kinds = SPECIALS.iter().map(|special| special.name).join(", ");
+            format!("Esta partida no tiene cartas de expansión. Los tipos que hay son: {}.\n", kinds)
+        },
+        "expansion" => game.rules.expansion
+            .iter()
+            .filter_map(|special| {
+                let value = ladder.values().nth(special.rank)?;
+                let description = match special.name {
+                    "Prism" => "Se intercambia como el color que mejor encaje con la otra carta, incluso para una \
+                        bonificación.",
+                    "Refund" => "Da cambio en comunes cuando ayuda a pagar más de lo que cuesta una compra.",
+                    "Peek" => "Te enseña la carta de arriba del mazo siguiente cuando la gastas.",
+                    _ => special.description,
+                };
+                Some(format!("{} ({:?}, una de cada color): {}\n", special.name, value, description))
+            })
+            .collect(),
+        _ => return None,
+    };
+
+    Some(text)
+}
+
+impl<Input: BufRead, Output: Write> Ui<Input, Output> {
+    pub(super) fn help(&mut self, name: Option<&str>) -> io::Result<()> {
+        match name {
+            None => {
+                for command in &COMMANDS {
+                    writeln!(self.output, "{:<10} {}", command.name, self.lang.pick(&command.summary))?;
+                }
+                self.say(Message::Escapes)
+            },
+            Some(name) => match COMMANDS.iter().find(|command| command.name == name) {
+                Some(command) => {
+                    let (usage, summary) = (self.lang.pick(&command.usage), self.lang.pick(&command.summary));
+                    self.say(Message::Usage(usage, summary))
+                },
+                None => self.say(Message::NoSuchCommand(name)),
+            },
+        }
+    }
+
+    pub(super) fn rules(&mut self, game: &Game, player: usize, topic: Option<&str>) -> io::Result<()> {
+        match topic {
+            None => {
+                for topic in &TOPICS {
+                    writeln!(self.output, "{}:", topic)?;
+                    write!(self.output, "{}", rule(topic, game, player, self.lang).unwrap_or_default())?;
+                }
+                Ok(())
+            },
+            Some(topic) => match rule(topic, game, player, self.lang) {
+                Some(text) => write!(self.output, "{}", text),
+                None => self.say(Message::NoSuchTopic(topic, &TOPICS.iter().join(", "))),
+            },
+        }
+    }
+}
@@ -0,0 +1,535 @@
+use std::env;
+
+use super::PromptError;
+use crate::game::{
+    action::{ActionError, Destination},
+    card::{Color, Value, ValueOrMonad},
+    goal::Goal,
+    rules::EndReason,
+    team::PassError,
+    BuyError,
+    DrawError,
+    FlipError,
+    LeapError,
+    SelectionError,
+    TradeError,
+};
+
+/// A language the `Ui` can talk in.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Lang {
+    English,
+    Spanish,
+}
+
+/// A fixed piece of text in every language, in the order of `Lang`.
+pub type Text = [&'static str; 2];
+
+/// Everything the `Ui` says, with whatever it says it about. Game terms such as colours, values and
+/// command names are left as they are, since they are also what the players type.
+pub enum Message<'a> {
+    TurnStart(usize),
+    Actions(&'a str),
+    PickColor(usize, &'a str),
+    AlreadyTaken(usize, Color),
+    NotAColor,
+    NoEarlierPick,
+    TurnOf(usize, usize),
+    NoDrawing,
+    Solved,
+    OutOfTurns,
+    NotRecognized(&'a str),
+    ImpossibleState(&'a str),
+    GameOver(Option<EndReason>),
+    Score(usize, Color, usize),
+    TeamScore(usize),
+    Wins(usize),
+    CantDraw,
+    UnableToDraw(&'a str),
+    CantFlip,
+    UnableToFlip(&'a str),
+    FirstToTrade,
+    SecondToTrade,
+    TakeBonus,
+    TradedMonad,
+    TradedCards(usize),
+    SelectToBuy,
+    MoreCards,
+    UnknownDeck(&'a str),
+    UnknownPreference(&'a str),
+    PayingWith(&'a str),
+    Bought(ValueOrMonad),
+    Change(usize),
+    Leapt,
+    Peek(Value, &'a str),
+    OnlyInTeams,
+    SelectToPass,
+    PartnerHasCard(usize),
+    Partner(Color),
+    PartnerHand(&'a str),
+    Identity(Color),
+    Hand(&'a str),
+    Table(&'a str),
+    WillDraw(&'a str),
+    WillGain(usize),
+    GoesTo(&'a str, &'a Destination),
+    HandPoints(usize, usize),
+    GoAhead,
+    YesNo(&'a str),
+    PleaseYesNo,
+    NotANumber,
+    NotValidSelection(usize),
+    AlreadyChosen(usize),
+    Cancelled,
+    Escapes,
+    SelectDeck(&'a str),
+    DeckEmpty,
+    InvalidInput,
+    NotEnoughCommons,
+    HowManyCommons(&'a str),
+    InvalidSelection,
+    OrderCommons(usize, usize),
+    TryAgain,
+    Usage(&'a str, &'a str),
+    NoSuchCommand(&'a str),
+    NoSuchTopic(&'a str, &'a str),
+    Lesson(usize, usize, &'a str),
+    LessonActions(&'a str, &'a str),
+    WellDone,
+    NotThisLesson,
+    NoCards,
+    CardNumber(usize),
+    Worth(usize),
+    Wild,
+    InBonusPair,
+    CommonDeck(usize),
+    DiscardPile(&'a str),
+    ValueDeck(Value, &'a str),
+    CommonLabel,
+    DiscardLabel,
+}
+
+impl Lang {
+    /// Reads a language code such as `es`, or a locale such as `es_ES.UTF-8`.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.split(['_', '-', '.']).next()?.to_ascii_lowercase().as_str() {
+            "en" => Some(Lang::English),
+            "es" => Some(Lang::Spanish),
+            _ => None,
+        }
+    }
+
+    /// The language of the locale the program runs in, and English when it has none we speak.
+    pub fn detect() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| Lang::from_code(&locale))
+            .unwrap_or(Lang::English)
+    }
+
+    pub fn pick(self, text: &Text) -> &'static str {
+        text[self as usize]
+    }
+
+    /// The name of a temperature group, which colour sets give in English.
+    pub fn group(self, name: &'static str) -> &'static str {
+        match (self, name) {
+            (Lang::Spanish, "warm")   => "cálido",
+            (Lang::Spanish, "cold")   => "frío",
+            (Lang::Spanish, "earthy") => "terroso",
+            _                         => name,
+        }
+    }
+
+    /// What a yes or no answer means, accepting English as well as this language.
+    pub fn answer(self, answer: &str) -> Option<bool> {
+        match (self, answer) {
+            (_, "yes") | (Lang::Spanish, "si") | (Lang::Spanish, "sí") => Some(true),
+            (_, "no") => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Whether an answer steps out of a dialogue rather than answering it, accepting English as well as
+    /// this language.
+    pub fn escape(self, answer: &str) -> Option<PromptError> {
+        match (self, answer) {
+            (_, "back") | (Lang::Spanish, "atrás") | (Lang::Spanish, "atras") => Some(PromptError::Back),
+            (_, "cancel") | (Lang::Spanish, "cancelar")                     => Some(PromptError::Cancelled),
+            _                                                               => None,
+        }
+    }
+
+    pub fn text(self, message: Message) -> String {
+        match self {
+            Lang::English => english(message),
+            Lang::Spanish => spanish(message),
+        }
+    }
+}
+
+fn english(message: Message) -> String {
+    use self::Message::*;
+    match message {
+        TurnStart(player)          => format!("It is now player {}'s turn!", player + 1),
+        Actions(names)             => format!("Actions: {} \n> ", names),
+        PickColor(seat, left)      => format!("Player {}, pick your colour ({}) > ", seat + 1, left),
+        AlreadyTaken(other, color) => format!("Player {} already took {}!", other + 1, color),
+        NotAColor                  => "That isn't a colour!".to_string(),
+        NoEarlierPick              => "Nobody has picked yet, so there's no pick to go back to.".to_string(),
+        TurnOf(turn, turns)        => format!("Turn {} of {}", turn, turns),
+        NoDrawing                  => "No drawing in this one!".to_string(),
+        Solved                     => "Solved!".to_string(),
+        OutOfTurns                 => "Out of turns.".to_string(),
+        NotRecognized(line)        => format!("Command not recognized: {}", line),
+        ImpossibleState(error)     => format!("The game is in an impossible state: {}", error),
+        GameOver(Some(reason))     => format!("The game is over! {}.", reason),
+        GameOver(None)             => "The game is over!".to_string(),
+        Score(i, identity, count)  => format!("Player {} ({}): {} monad(s)", i + 1, identity, count),
+        TeamScore(score)           => format!(", {} for the team", score),
+        Wins(winner)               => format!("Player {} wins!", winner + 1),
+        CantDraw                   => "You can't draw, you already did something else this turn!".to_string(),
+        UnableToDraw(error)        => format!("Unable to draw: {}", error),
+        CantFlip                   => "You can't flip, you already did something else this turn!".to_string(),
+        UnableToFlip(error)        => format!("Unable to flip: {}", error),
+        FirstToTrade               => "Please select the first card to trade!".to_string(),
+        SecondToTrade              => "Please select the second card to trade!".to_string(),
+        TakeBonus                  => "Woah! You can take a bonus! Do you want to?".to_string(),
+        TradedMonad                => "You traded for a monad!".to_string(),
+        TradedCards(count)         => format!("You traded for {} card(s)!", count),
+        SelectToBuy                => "Select a card you want to use to buy!".to_string(),
+        MoreCards                  => "More cards?".to_string(),
+        UnknownDeck(deck)          => format!("Unknown deck: {}", deck),
+        UnknownPreference(word)    => format!("Pay with 'keep' or 'spend', not {}", word),
+        PayingWith(cards)          => format!("Paying with: {}", cards),
+        Bought(ValueOrMonad::Value(_)) => "Player bought a card!".to_string(),
+        Bought(ValueOrMonad::Monad)    => "Player bought a Monad!".to_string(),
+        Change(count)              => format!("You got {} common(s) back as change!", count),
+        Leapt                      => "Player leapt ahead and drew a card!".to_string(),
+        Peek(value, card)          => format!("You peek at the top of the {:?} deck: {}", value, card),
+        OnlyInTeams                => "You can only pass cards when playing in teams!".to_string(),
+        SelectToPass               => "Select a card to pass to your partner!".to_string(),
+        PartnerHasCard(partner)    => format!("Player {} has the card now!", partner + 1),
+        Partner(identity)          => format!("Partner: {}", identity),
+        PartnerHand(hand)          => format!("Partner's hand: {}", hand),
+        Identity(identity)         => format!("Color: {}", identity),
+        Hand(hand)                 => format!("Hand: {}", hand),
+        Table(table)               => format!("Table: {}", table),
+        WillDraw(values)           => format!("You will draw: {}", values),
+        WillGain(monads)           => format!("You will gain {} monad(s)", monads),
+        GoesTo(card, destination)  => format!("{} goes to {}", card, destination),
+        HandPoints(before, after)  => format!("Hand points: {} -> {}", before, after),
+        GoAhead                    => "Go ahead?".to_string(),
+        YesNo(question)            => format!("{} (yes/no) > ", question),
+        PleaseYesNo                => "Please enter 'yes' or 'no' > ".to_string(),
+        NotANumber                 => "What you entered is not an unsigned integer! Please try again.".to_string(),
+        NotValidSelection(index)   => format!("{} is not a valid selection!", index),
+        AlreadyChosen(index)       => format!("Card {} is already chosen!", index),
+        Cancelled                  => "Cancelled, nothing was done.".to_string(),
+        Escapes                    => {
+            "In the middle of an action, 'back' asks the previous question again and 'cancel' gives up.".to_string()
+        },
+        SelectDeck(decks)          => format!("Select a deck ({} Monad) > ", decks),
+        DeckEmpty                  => "That deck is out of cards!".to_string(),
+        InvalidInput               => "Invalid input!".to_string(),
+        NotEnoughCommons           => "Not enough commons to leap!".to_string(),
+        HowManyCommons(targets)    => format!("Enter how many commons you want to trade! ({})", targets),
+        InvalidSelection           => "Invalid selection.".to_string(),
+        OrderCommons(count, accept) => format!(
+            "Here are all the commons to select.\
+            The first {} cards on the left will be traded in.\n\
+            Enter the number of the card to move it left.\n\
+            Enter {} to accept selection.\n",
+            count, accept,
+        ),
+        TryAgain                   => "Not a valid selection! Please try again.".to_string(),
+        Usage(usage, summary)      => format!("Usage: {}\n{}", usage, summary),
+        NoSuchCommand(name)        => format!("No such command: {}. Try 'help'.", name),
+        NoSuchTopic(topic, topics) => format!("No such topic: {}. Topics: {}", topic, topics),
+        Lesson(i, count, title)    => format!("Lesson {} of {}: {}", i + 1, count, title),
+        LessonActions(task, names) => format!("{}\nActions: {} skip quit \n> ", task, names),
+        WellDone                   => "Well done!\n".to_string(),
+        NotThisLesson              => {
+            "That works too, but it isn't what this lesson is about. Let's try again.".to_string()
+        },
+        NoCards                    => "no cards".to_string(),
+        CardNumber(i)              => format!("card {}", i),
+        Worth(points)              => format!("worth {}", points),
+        Wild                       => "wild".to_string(),
+        InBonusPair                => "in a bonus pair".to_string(),
+        CommonDeck(count)          => format!("Common deck: {} cards left.", count),
+        DiscardPile(cards)         => format!("Discard pile: {}", cards),
+        ValueDeck(value, cards)    => format!("{:?} deck: {}", value, cards),
+        CommonLabel                => "Common:".to_string(),
+        DiscardLabel               => "Discard:".to_string(),
+    }
+}
+
+fn spanish(message: Message) -> String {
+    use self::Message::*;
+    let lang = Lang::Spanish;
+    match message {
+        TurnStart(player)          => format!("¡Es el turno del jugador {}!", player + 1),
+        Actions(names)             => format!("Acciones: {} \n> ", names),
+        PickColor(seat, left)      => format!("Jugador {}, elige tu color ({}) > ", seat + 1, left),
+        AlreadyTaken(other, color) => format!("¡El jugador {} ya eligió {}!", other + 1, color),
+        NotAColor                  => "¡Eso no es un color!".to_string(),
+        NoEarlierPick              => "Nadie ha elegido todavía, así que no hay elección a la que volver.".to_string(),
+        TurnOf(turn, turns)        => format!("Turno {} de {}", turn, turns),
+        NoDrawing                  => "¡En este no se puede robar!".to_string(),
+        Solved                     => "¡Resuelto!".to_string(),
+        OutOfTurns                 => "No quedan turnos.".to_string(),
+        NotRecognized(line)        => format!("Orden desconocida: {}", line),
+        ImpossibleState(error)     => format!("La partida está en un estado imposible: {}", error),
+        GameOver(Some(reason))     => format!("¡La partida ha terminado! {}.", reason.localize(lang)),
+        GameOver(None)             => "¡La partida ha terminado!".to_string(),
+        Score(i, identity, count)  => format!("Jugador {} ({}): {} mónada(s)", i + 1, identity, count),
+        TeamScore(score)           => format!(", {} para el equipo", score),
+        Wins(winner)               => format!("¡Gana el jugador {}!", winner + 1),
+        CantDraw                   => "¡No puedes robar, ya has hecho otra cosa este turno!".to_string(),
+        UnableToDraw(error)        => format!("No se puede robar: {}", error),
+        CantFlip                   => "¡No puedes dar la vuelta, ya has hecho otra cosa este turno!".to_string(),
+        UnableToFlip(error)        => format!("No se puede dar la vuelta: {}", error),
+        FirstToTrade               => "¡Elige la primera carta para intercambiar!".to_string(),
+        SecondToTrade              => "¡Elige la segunda carta para intercambiar!".to_string(),
+        TakeBonus                  => "¡Vaya! Puedes llevarte una bonificación. ¿La quieres?".to_string(),
+        TradedMonad                => "¡Has conseguido una mónada!".to_string(),
+        TradedCards(count)         => format!("¡Has conseguido {} carta(s)!", count),
+        SelectToBuy                => "¡Elige una carta con la que pagar!".to_string(),
+        MoreCards                  => "¿Más cartas?".to_string(),
+        UnknownDeck(deck)          => format!("Mazo desconocido: {}", deck),
+        UnknownPreference(word)    => format!("Paga con 'keep' o 'spend', no con {}", word),
+        PayingWith(cards)          => format!("Pagas con: {}", cards),
+        Bought(ValueOrMonad::Value(_)) => "¡El jugador ha comprado una carta!".to_string(),
+        Bought(ValueOrMonad::Monad)    => "¡El jugador ha comprado una mónada!".to_string(),
+        Change(count)              => format!("¡Recibes {} común(es) de cambio!", count),
+        Leapt                      => "¡El jugador ha saltado y ha robado una carta!".to_string(),
+        Peek(value, card)          => format!("Miras la carta de arriba del mazo {:?}: {}", value, card),
+        OnlyInTeams                => "¡Solo se pueden pasar cartas jugando por equipos!".to_string(),
+        SelectToPass               => "¡Elige una carta para pasar a tu compañero!".to_string(),
+        PartnerHasCard(partner)    => format!("¡El jugador {} ya tiene la carta!", partner + 1),
+        Partner(identity)          => format!("Compañero: {}", identity),
+        PartnerHand(hand)          => format!("Mano del compañero: {}", hand),
+        Identity(identity)         => format!("Color: {}", identity),
+        Hand(hand)                 => format!("Mano: {}", hand),
+        Table(table)               => format!("Mesa: {}", table),
+        WillDraw(values)           => format!("Robarás: {}", values),
+        WillGain(monads)           => format!("Ganarás {} mónada(s)", monads),
+        GoesTo(card, destination)  => format!("{} va a {}", card, destination.localize(lang)),
+        HandPoints(before, after)  => format!("Puntos en la mano: {} -> {}", before, after),
+        GoAhead                    => "¿Adelante?".to_string(),
+        YesNo(question)            => format!("{} (sí/no) > ", question),
+        PleaseYesNo                => "Responde 'sí' o 'no' > ".to_string(),
+        NotANumber                 => "¡Eso no es un número entero sin signo! Inténtalo de nuevo.".to_string(),
+        NotValidSelection(index)   => format!("¡{} no es una opción válida!", index),
+        AlreadyChosen(index)       => format!("¡La carta {} ya está elegida!", index),
+        Cancelled                  => "Cancelado, no se ha hecho nada.".to_string(),
+        Escapes                    => {
+            "A mitad de una acción, 'atrás' repite la pregunta anterior y 'cancelar' la abandona.".to_string()
+        },
+        SelectDeck(decks)          => format!("Elige un mazo ({} Monad) > ", decks),
+        DeckEmpty                  => "¡Ese mazo no tiene cartas!".to_string(),
+        InvalidInput               => "¡Entrada no válida!".to_string(),
+        NotEnoughCommons           => "¡No tienes comunes suficientes para saltar!".to_string(),
+        HowManyCommons(targets)    => format!("¡Escribe cuántas comunes quieres intercambiar! ({})", targets),
+        InvalidSelection           => "Opción no válida.".to_string(),
+        OrderCommons(count, accept) => format!(
+            "Estas son todas las comunes que puedes elegir. \
+            Se intercambiarán las {} primeras cartas de la izquierda.\n\
+            Escribe el número de una carta para moverla a la izquierda.\n\
+            Escribe {} para aceptar la selección.\n",
+            count, accept,
+        ),
+        TryAgain                   => "¡Opción no válida! Inténtalo de nuevo.".to_string(),
+        Usage(usage, summary)      => format!("Uso: {}\n{}", usage, summary),
+        NoSuchCommand(name)        => format!("No existe la orden {}. Prueba con 'help'.", name),
+        NoSuchTopic(topic, topics) => format!("No existe el tema {}. Temas: {}", topic, topics),
+        Lesson(i, count, title)    => format!("Lección {} de {}: {}", i + 1, count, title),
+        LessonActions(task, names) => format!("{}\nAcciones: {} skip quit \n> ", task, names),
+        WellDone                   => "¡Muy bien!\n".to_string(),
+        NotThisLesson              => "Eso también vale, pero esta lección no va de eso. Prueba otra vez.".to_string(),
+        NoCards                    => "sin cartas".to_string(),
+        CardNumber(i)              => format!("carta {}", i),
+        Worth(points)              => format!("vale {}", points),
+        Wild                       => "comodín".to_string(),
+        InBonusPair                => "en una pareja de bonificación".to_string(),
+        CommonDeck(count)          => format!("Mazo común: quedan {} cartas.", count),
+        DiscardPile(cards)         => format!("Pila de descarte: {}", cards),
+        ValueDeck(value, cards)    => format!("Mazo {:?}: {}", value, cards),
+        CommonLabel                => "Comunes:".to_string(),
+        DiscardLabel               => "Descarte:".to_string(),
+    }
+}
+
+/// Text from the engine, such as its errors, which speaks English through its own `Display`.
+pub trait Localize {
+    fn localize(&self, lang: Lang) -> String;
+}
+
+impl Localize for EndReason {
+    fn localize(&self, lang: Lang) -> String {
+        use self::EndReason::*;
+        match lang {
+            Lang::English => self.to_string(),
+            Lang::Spanish => match self {
+                MonadsGone => "Se han llevado todas las mónadas",
+                Stalemate  => "Nadie puede hacer nada más",
+                TurnLimit  => "Se ha llegado al límite de turnos",
+            }.to_string(),
+        }
+    }
+}
+
+impl Localize for Goal {
+    fn localize(&self, lang: Lang) -> String {
+        if lang == Lang::English {
+            return self.to_string();
+        }
+        let mut text = match self.target {
+            ValueOrMonad::Value(value) => format!("Consigue un {:?}", value),
+            ValueOrMonad::Monad => "Consigue una mónada".to_string(),
+        };
+        match self.turns {
+            1 => text += " en este turno",
+            turns => text += &format!(" en {} turnos como mucho", turns),
+        }
+        if !self.draws {
+            text += " sin robar";
+        }
+        text
+    }
+}
+
+impl Localize for Destination {
+    fn localize(&self, lang: Lang) -> String {
+        match (lang, self) {
+            (Lang::English, _)                      => self.to_string(),
+            (Lang::Spanish, Destination::Discard)   => "la pila de descarte".to_string(),
+            (Lang::Spanish, Destination::Bottom(value)) => format!("el fondo del mazo {:?}", value),
+            (Lang::Spanish, Destination::Partner(partner)) => format!("tu compañero, el jugador {}", partner + 1),
+        }
+    }
+}
+
+impl Localize for SelectionError {
+    fn localize(&self, lang: Lang) -> String {
+        use self::SelectionError::*;
+        match (lang, self) {
+            (Lang::English, _)                    => self.to_string(),
+            (Lang::Spanish, NoSuchCard(index))    => format!("No tienes ninguna carta {} en la mano", index),
+            (Lang::Spanish, DuplicateCard(index)) => format!("La carta {} se ha elegido más de una vez", index),
+        }
+    }
+}
+
+impl Localize for DrawError {
+    fn localize(&self, lang: Lang) -> String {
+        match (lang, self.discarded) {
+            (Lang::English, _)         => self.to_string(),
+            (Lang::Spanish, 0)         => "No quedan comunes que robar ni a las que dar la vuelta".to_string(),
+            (Lang::Spanish, discarded) => {
+                format!("El mazo común está vacío; dale antes la vuelta a las {} carta(s) del descarte", discarded)
+            },
+        }
+    }
+}
+
+impl Localize for FlipError {
+    fn localize(&self, lang: Lang) -> String {
+        use self::FlipError::*;
+        match (lang, self) {
+            (Lang::English, _)                         => self.to_string(),
+            (Lang::Spanish, EmptyDiscardPile)          => "La pila de descarte está vacía".to_string(),
+            (Lang::Spanish, NonEmptyCommonDeck(count)) => {
+                format!("Al mazo común aún le quedan {} carta(s)", count)
+            },
+        }
+    }
+}
+
+impl Localize for LeapError {
+    fn localize(&self, lang: Lang) -> String {
+        use self::LeapError::*;
+        match (lang, self) {
+            (Lang::English, _)                    => self.to_string(),
+            (_, Selection(error))                 => error.localize(lang),
+            (Lang::Spanish, NumOfCards(length))   => format!("Número de cartas incorrecto: {}", length),
+            (Lang::Spanish, NotAllCommons(index)) => {
+                format!("No todas las cartas son comunes: la carta {} no lo es", index)
+            },
+            (Lang::Spanish, OutOfCards(value))    => format!("¡El mazo {:?} no tiene cartas!", value),
+        }
+    }
+}
+
+impl Localize for TradeError {
+    fn localize(&self, lang: Lang) -> String {
+        use self::TradeError::*;
+        match (lang, self) {
+            (Lang::English, _)                 => self.to_string(),
+            (_, Selection(error))              => error.localize(lang),
+            (Lang::Spanish, OutOfCards(value)) => format!("¡El mazo {:?} no tiene cartas!", value),
+            (Lang::Spanish, OutOfMonads)       => "¡No quedan mónadas!".to_string(),
+            (Lang::Spanish, SameTemperature(first, other)) => {
+                format!("Las cartas {} y {} son de la misma temperatura, y no pueden serlo", first, other)
+            },
+            (Lang::Spanish, NotSameValueOrIdentity(first, other)) => format!(
+                "Las cartas deben tener el mismo valor, o una debe ser de tu color, no {:?} y {:?}",
+                first, other,
+            ),
+            (Lang::Spanish, NoBonus)           => "Esas cartas no dan bonificación".to_string(),
+        }
+    }
+}
+
+impl Localize for BuyError {
+    fn localize(&self, lang: Lang) -> String {
+        use self::BuyError::*;
+        match (lang, self) {
+            (Lang::English, _)                 => self.to_string(),
+            (_, Selection(error))              => error.localize(lang),
+            (Lang::Spanish, SameValue { card, price }) => format!(
+                "¡No se puede comprar algo del mismo valor! La carta {} vale {} o más",
+                card, price,
+            ),
+            (Lang::Spanish, NotEnoughPoints { price, offered }) => {
+                format!("¡No tienes puntos suficientes! Ofreces {} por {}", offered, price)
+            },
+            (Lang::Spanish, OutOfCards(value)) => format!("¡El mazo {:?} no tiene cartas!", value),
+            (Lang::Spanish, OutOfMonads)       => "¡No quedan mónadas!".to_string(),
+        }
+    }
+}
+
+impl Localize for PassError {
+    fn localize(&self, lang: Lang) -> String {
+        use self::PassError::*;
+        match (lang, self) {
+            (Lang::English, _)                   => self.to_string(),
+            (_, Selection(error))                => error.localize(lang),
+            (Lang::Spanish, NoPartner)           => "No tienes compañero a quien pasar cartas".to_string(),
+            (Lang::Spanish, LimitReached(limit)) => format!("Solo puedes pasar {} carta(s) por turno", limit),
+        }
+    }
+}
+
+impl Localize for ActionError {
+    fn localize(&self, lang: Lang) -> String {
+        use self::ActionError::*;
+        match (lang, self) {
+            (Lang::English, _)             => self.to_string(),
+            (_, Draw(error))               => error.localize(lang),
+            (_, Flip(error))               => error.localize(lang),
+            (_, Trade(error))              => error.localize(lang),
+            (_, Buy(error))                => error.localize(lang),
+            (_, Leap(error))               => error.localize(lang),
+            (_, Pass(error))               => error.localize(lang),
+            (Lang::Spanish, GameOver)      => "¡La partida ha terminado!".to_string(),
+            (Lang::Spanish, AlreadyActed)  => "¡Ya has hecho otra cosa este turno!".to_string(),
+            (Lang::Spanish, CannotEnd)     => "¡Aún no puedes terminar el turno! Todavía puedes hacer algo.".to_string(),
+            (Lang::Spanish, Broken(error)) => format!("Eso rompió la partida: {}", error),
+        }
+    }
+}
@@ -1,232 +1,579 @@
+use itertools::Itertools;
 use std::{
+    error,
     fmt,
-    io::{self, BufRead, Write},
+    io::{self, BufRead, ErrorKind, Write},
 };
 use crate::game::{
     Game,
-    card::{Deck, Value, Monad, ValueOrMonad},
+    NumPlayers,
+    card::{Color, ColorSet, Deck, Monad, ValueOrMonad},
+    identity::draft_order,
+    payment::PaymentPreference,
+    action::{Action, ActionError, Outcome},
+    goal::Goal,
+    notation::Move,
+    record::Record,
 };
+use self::{
+    lang::{Lang, Localize, Message},
+    style::Style,
+};
+
+mod help;
+mod tutorial;
+pub mod lang;
+pub mod style;
+
+#[cfg(test)]
+mod tests;
 
 pub struct Ui<Input, Output> {
     pub input: Input,
     pub output: Output,
+    /// Validate the game after every command, even in release builds.
+    pub paranoid: bool,
+    /// Where moves are written down, if anywhere.
+    pub record: Option<Record>,
+    pub style: Style,
+    pub lang: Lang,
 }
 
 impl<Input: BufRead, Output: Write> Ui<Input, Output> {
-    pub fn play(mut self, mut game: Game) -> io::Result<()> {
+    /// Runs turns until the game is over or the input runs dry, handing the game back either way.
+    pub fn play(&mut self, mut game: Game) -> io::Result<Game> {
         let mut buffer = String::new();
 
-        for player in (0..game.players.len()).cycle() {
-            let mut action_performed = false;
+        while !game.is_over() {
+            let player = game.turn;
+            self.say(Message::TurnStart(player))?;
 
-            writeln!(self.output, "It is now player {}'s turn!", player + 1).unwrap();
-            *game.player_took_bonus(player) = false;
-
-            loop {
+            // The game can end part way through a turn, when the last monad is taken.
+            while game.turn == player && !game.is_over() {
                 buffer.clear();
-                write!(self.output, "Actions: show draw flip trade buy leap end \n> ").unwrap();
-                self.output.flush().unwrap();
-                self.input.read_line(&mut buffer).unwrap();
-
-                match buffer.trim() {
-                    "show" => self.print_state(&game, player)?,
-                    "draw" => {
-                        if action_performed {
-                            writeln!(self.output, "You can't draw, you already did something else this turn!")?;
-                            continue;
-                        }
-                        self.draw(&mut game, player)?;
-                        break;
-                    },
-                    "flip" => {
-                        if action_performed {
-                            writeln!(self.output, "You can't flip, you already did something else this turn!")?;
-                            continue;
-                        }
-                        self.flip(&mut game)?;
-                        break;
-                    },
-                    "trade" => action_performed |= self.trade(&mut game, player)?.is_ok(),
-                    "buy" => action_performed |= self.buy(&mut game, player)?.is_ok(),
-                    "leap" => action_performed |= self.leap(&mut game, player)?.is_ok(),
-                    "end" => {
-                        if action_performed || game.check_player_end(player) {
-                            break;
-                        }
-                        writeln!(self.output, "You can't end your turn yet! You can still take an action.")?;
+                write!(self.output, "{}", self.lang.text(Message::Actions(&help::command_names())))?;
+                self.output.flush()?;
+                if self.input.read_line(&mut buffer)? == 0 {
+                    return Ok(game);
+                }
+
+                self.command(&mut game, player, &buffer)?;
+            }
+        }
+
+        self.print_results(&game)?;
+
+        Ok(game)
+    }
+
+    /// Lets the players pick their identities from `colors`, the last seat first, handing back each
+    /// seat's colour. Going back lets the seat before pick again, and cancelling stops the draft.
+    pub fn draft(&mut self, num_players: NumPlayers, colors: &'static ColorSet) -> Result<Vec<Color>, PromptError> {
+        let mut picks: Vec<Option<Color>> = vec![None; num_players as usize];
+        let mut buffer = String::new();
+        let order = draft_order(num_players).collect::<Vec<_>>();
+        let mut turn = 0;
+
+        while let Some(&seat) = order.get(turn) {
+            let left = colors.colors().filter(|color| !picks.contains(&Some(*color))).join(" ");
+            buffer.clear();
+            write!(self.output, "{}", self.lang.text(Message::PickColor(seat, &left)))?;
+            self.output.flush()?;
+
+            match self.read_step(&mut buffer) {
+                Err(PromptError::Back) => {
+                    match turn.checked_sub(1) {
+                        Some(before) => {
+                            picks[order[before]] = None;
+                            turn = before;
+                        },
+                        None => self.say(Message::NoEarlierPick)?,
+                    }
+                    continue;
+                },
+                result => result?,
+            }
+
+            match colors.color(buffer.trim()) {
+                Some(color) => match picks.iter().position(|&pick| pick == Some(color)) {
+                    Some(other) => self.say(Message::AlreadyTaken(other, color))?,
+                    None => {
+                        picks[seat] = Some(color);
+                        turn += 1;
                     },
-                    command => writeln!(self.output, "Command not recognized: {}", command)?,
+                },
+                None => self.say(Message::NotAColor)?,
+            }
+        }
+
+        Ok(picks.into_iter().map(|pick| pick.expect("Every seat picks")).collect())
+    }
+
+    /// Plays out `goal` for the current player while everyone else passes, handing back whether
+    /// it was reached in time.
+    pub fn challenge(&mut self, mut game: Game, goal: &Goal) -> io::Result<bool> {
+        let mut buffer = String::new();
+        let start = game.clone();
+        let player = game.turn;
+        writeln!(self.output, "{}.", goal.localize(self.lang))?;
+
+        for turn in 1..=goal.turns {
+            if goal.turns > 1 {
+                self.say(Message::TurnOf(turn, goal.turns))?;
+            }
+
+            while game.turn == player && !game.is_over() {
+                buffer.clear();
+                let names = format!("{} give up", help::command_names());
+                write!(self.output, "{}", self.lang.text(Message::Actions(&names)))?;
+                self.output.flush()?;
+                if self.input.read_line(&mut buffer)? == 0 {
+                    return Ok(false);
+                }
+
+                match buffer.split_whitespace().collect::<Vec<_>>().as_slice() {
+                    ["give", "up"] => return Ok(false),
+                    ["draw"] if !goal.allows(&Action::Draw) => self.say(Message::NoDrawing)?,
+                    _ => self.command(&mut game, player, &buffer)?,
+                }
+
+                if goal.met(&start, &game, player) {
+                    self.say(Message::Solved)?;
+                    return Ok(true);
                 }
             }
+
+            game.skip_to(player);
         }
 
-        Ok(())
+        self.say(Message::OutOfTurns)?;
+        Ok(false)
     }
 
+    /// Carries out one line of input for `player`.
+    fn command(&mut self, game: &mut Game, player: usize, line: &str) -> io::Result<()> {
+        match self.dispatch(game, player, line) {
+            Ok(()) => {},
+            Err(PromptError::Back) | Err(PromptError::Cancelled) => self.say(Message::Cancelled)?,
+            Err(PromptError::Io(error)) => return Err(error),
+        }
 
-    fn draw(&mut self, game: &mut Game, player: usize) -> io::Result<()> {
-        if game.draw(player).is_err() {
-            writeln!(self.output, "Unable to draw: no commons left")?;
+        if self.paranoid {
+            if let Err(error) = game.validate() {
+                self.say(Message::ImpossibleState(&error.to_string()))?;
+                return Err(io::Error::other(error));
+            }
         }
 
         Ok(())
     }
 
-    fn flip(&mut self, game: &mut Game) -> io::Result<()> {
-        if let Err(error) = game.flip() {
-            writeln!(self.output, "Unable to flip: {}", error)?;
+    fn dispatch(&mut self, game: &mut Game, player: usize, line: &str) -> Result<(), PromptError> {
+        match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["show"] => self.print_state(game, player)?,
+            ["position"] => writeln!(self.output, "{}", game.position())?,
+            ["draw"] => self.draw(game)?,
+            ["flip"] => self.flip(game)?,
+            ["trade"] => self.trade(game, player)?,
+            ["buy"] => self.buy(game, player)?,
+            ["buy", "auto", deck] => self.buy_auto(game, player, deck, PaymentPreference::keep_all())?,
+            ["buy", "auto", deck, preference] => match preference.parse() {
+                Ok(preference) => self.buy_auto(game, player, deck, preference)?,
+                Err(_) => self.say(Message::UnknownPreference(preference))?,
+            },
+            ["leap"] => self.leap(game, player)?,
+            ["pass"] => self.pass(game, player)?,
+            ["help"] => self.help(None)?,
+            ["help", name] => self.help(Some(name))?,
+            ["rules"] => self.rules(game, player, None)?,
+            ["rules", topic] => self.rules(game, player, Some(topic))?,
+            ["end"] => {
+                if let Err(error) = self.perform(game, Action::End) {
+                    writeln!(self.output, "{}", error.localize(self.lang))?;
+                }
+            },
+            _ => self.say(Message::NotRecognized(line.trim()))?,
         }
 
         Ok(())
     }
 
-    fn trade(&mut self, game: &mut Game, player: usize) -> io::Result<Result<(), ()>> {
-        let player_ref = &game.players[player];
-        let card1 = self.prompt_hand_selection(game, player, &"Please select the first card to trade!")?;
-        let card2 = self.prompt_hand_selection(game, player, &"Please select the second card to trade!")?;
+    /// Performs `action` for the current player, writing it down in the record.
+    fn perform(&mut self, game: &mut Game, action: Action) -> Result<Outcome, ActionError> {
+        let before = self.record.as_ref().map(|_| game.clone());
+        let player = game.turn;
+        let result = game.perform(action.clone());
 
-        let bonus =
-            if player_ref.can_take_bonus(card1, card2) {
-                self.prompt_bool(&"Woah! You can take a bonus! Do you want to?")?
-            } else {
-                false
-            };
+        if let (Some(record), Some(before), Ok(outcome)) = (&mut self.record, before, &result) {
+            record.push(Move::record(&before, game, player, &action, outcome));
+        }
 
-        let result = match game.trade(player, card1, card2, bonus) {
-            Ok((count, monad)) => {
-                if monad { writeln!(self.output, "You traded for a monad!")?; }
-                writeln!(self.output, "You traded for {} card(s)!", count)?;
-                Ok(())
-            },
-            Err(error) => {
-                writeln!(self.output, "{}", error)?;
-                Err(())
-            },
+        result
+    }
+
+    fn print_results(&mut self, game: &Game) -> io::Result<()> {
+        self.say(Message::GameOver(game.end_reason()))?;
+        for (i, player) in game.players.iter().enumerate() {
+            write!(self.output, "{}", self.lang.text(Message::Score(i, player.identity, player.monads.len())))?;
+            match game.partner(i) {
+                Some(_) => self.say(Message::TeamScore(game.score(i)))?,
+                None => writeln!(self.output)?,
+            }
+        }
+        for winner in game.winners() {
+            self.say(Message::Wins(winner))?;
+        }
+
+        Ok(())
+    }
+
+
+    fn draw(&mut self, game: &mut Game) -> io::Result<()> {
+        match self.perform(game, Action::Draw) {
+            Ok(_) => Ok(()),
+            Err(ActionError::AlreadyActed) => self.say(Message::CantDraw),
+            Err(error) => self.say(Message::UnableToDraw(&error.localize(self.lang))),
+        }
+    }
+
+    fn flip(&mut self, game: &mut Game) -> io::Result<()> {
+        match self.perform(game, Action::Flip) {
+            Ok(_) => Ok(()),
+            Err(ActionError::AlreadyActed) => self.say(Message::CantFlip),
+            Err(error) => self.say(Message::UnableToFlip(&error.localize(self.lang))),
+        }
+    }
+
+    fn trade(&mut self, game: &mut Game, player: usize) -> Result<(), PromptError> {
+        let action = 'first: loop {
+            let card1 = self.prompt_hand_selection(game, player, Message::FirstToTrade, &[])?;
+            loop {
+                let card2 = match back(self.prompt_hand_selection(game, player, Message::SecondToTrade, &[card1]))? {
+                    Some(card2) => card2,
+                    None => continue 'first,
+                };
+
+                let bonus =
+                    if game.players[player].can_take_bonus(card1, card2) {
+                        match back(self.prompt_bool(Message::TakeBonus))? {
+                            Some(bonus) => bonus,
+                            None => continue,
+                        }
+                    } else {
+                        false
+                    };
+
+                let action = Action::Trade { card1, card2, bonus };
+                match back(self.confirm(game, player, &action))? {
+                    Some(true) => break 'first action,
+                    Some(false) => return Ok(()),
+                    None => continue,
+                }
+            }
         };
 
-        Ok(result)
+        match self.perform(game, action) {
+            Ok(outcome) => {
+                if outcome.monads > 0 { self.say(Message::TradedMonad)?; }
+                self.say(Message::TradedCards(outcome.cards))?;
+                self.reveal(&outcome)?;
+            },
+            Err(error) => writeln!(self.output, "{}", error.localize(self.lang))?,
+        }
+        Ok(())
     }
 
-    fn buy(&mut self, game: &mut Game, player: usize) -> io::Result<Result<(), ()>> {
+    /// Asks for the cards to pay with one at a time, where going back puts the last one back in the hand.
+    fn buy(&mut self, game: &mut Game, player: usize) -> Result<(), PromptError> {
+        let hand = game.players[player].hand.len();
         let mut cards: Vec<usize> = Vec::new();
-        loop {
-            cards.push(
-                self.prompt_hand_selection(
-                    game,
-                    player,
-                    &"Select a card you want to use to buy!"
-                )?
-            );
-            if !self.prompt_bool(&"More cards?")? { break; }
-        }
-
-        cards.sort();
-        cards.dedup();
-        let deck_value = self.prompt_value_or_monad(&game)?;
-
-        let drew_card = match game.buy(player, &mut cards, deck_value) {
-            Ok(drew_card) => drew_card,
-            Err(buy_error) => {
-                writeln!(self.output, "{}", buy_error)?;
-                return Ok(Err(()));
-            },
-        };
 
-        writeln!(
-            self.output,
-            "Player bought a {}!",
-            if drew_card { "card" } else { "Monad" }
-        )?;
+        'cards: loop {
+            if cards.len() < hand {
+                match back(self.prompt_hand_selection(game, player, Message::SelectToBuy, &cards))? {
+                    Some(card) => cards.push(card),
+                    None if cards.pop().is_some() => continue,
+                    None => return Err(PromptError::Back),
+                }
+            }
+            'more: loop {
+                if cards.len() < hand {
+                    match back(self.prompt_bool(Message::MoreCards))? {
+                        Some(true) => continue 'cards,
+                        Some(false) => {},
+                        None => {
+                            cards.pop();
+                            continue 'cards;
+                        },
+                    }
+                }
 
-        Ok(Ok(()))
+                loop {
+                    // Going back from the deck asks about more cards again, or takes back the last
+                    // card once the whole hand is chosen.
+                    let target = match back(self.prompt_value_or_monad(game))? {
+                        Some(target) => target,
+                        None if cards.len() < hand => continue 'more,
+                        None => {
+                            cards.pop();
+                            continue 'cards;
+                        },
+                    };
+
+                    let action = Action::Buy { cards: cards.clone(), target };
+                    match back(self.confirm(game, player, &action))? {
+                        Some(true) => return Ok(self.perform_buy(game, action)?),
+                        Some(false) => return Ok(()),
+                        None => continue,
+                    }
+                }
+            }
+        }
     }
 
-    fn leap(&mut self, game: &mut Game, player: usize) -> io::Result<Result<(), ()>> {
-        let mut cards = match self.prompt_leap(game, player)? {
+    fn buy_auto(
+        &mut self,
+        game: &mut Game,
+        player: usize,
+        deck: &str,
+        preference: PaymentPreference,
+    ) -> Result<(), PromptError> {
+        let deck_value = if deck.parse::<Monad>().is_ok() {
+            ValueOrMonad::Monad
+        } else if let Some(value) = game.table.ladder.value(deck) {
+            ValueOrMonad::Value(value)
+        } else {
+            return Ok(self.say(Message::UnknownDeck(deck))?);
+        };
+
+        let cards = match game.optimal_payment(player, deck_value, preference) {
             Ok(cards) => cards,
-            Err(()) => {
-                return Ok(Err(()));
-            }
+            Err(buy_error) => return Ok(writeln!(self.output, "{}", buy_error.localize(self.lang))?),
+        };
+
+        let paying = Deck::from(game.players[player].indexes_to_cards(&cards));
+        self.say(Message::PayingWith(&self.style.deck(&paying, self.lang)))?;
+
+        let action = Action::Buy { cards, target: deck_value };
+        if self.confirm(game, player, &action)? {
+            self.perform_buy(game, action)?;
+        }
+        Ok(())
+    }
+
+    fn perform_buy(&mut self, game: &mut Game, action: Action) -> io::Result<()> {
+        let target = match action {
+            Action::Buy { target, .. } => target,
+            _ => ValueOrMonad::Monad,
         };
 
-        let result = match game.leap(player, &mut cards) {
-            Ok(()) => {
-                writeln!(self.output, "Player leapt ahead and drew a card!")?;
-                Ok(())
+        match self.perform(game, action) {
+            Ok(outcome) => {
+                self.say(Message::Bought(target))?;
+                let change = outcome.cards - (outcome.monads == 0) as usize;
+                if change > 0 {
+                    self.say(Message::Change(change))?;
+                }
+                self.reveal(&outcome)
             },
-            Err(error) => {
-                writeln!(self.output, "{}", error)?;
-                Err(())
+            Err(error) => writeln!(self.output, "{}", error.localize(self.lang)),
+        }
+    }
+
+    fn leap(&mut self, game: &mut Game, player: usize) -> Result<(), PromptError> {
+        let commons: Vec<usize> = game.players[player]
+            .hand
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| card.is_common())
+            .map(|(i, _)| i)
+            .collect();
+
+        if commons.len() < *game.table.ladder.leaps().start() {
+            return Ok(self.say(Message::NotEnoughCommons)?);
+        }
+
+        let action = 'count: loop {
+            let count = self.prompt_leap_count(game, commons.len())?;
+            loop {
+                let cards = match back(self.prompt_leap_order(game, player, commons.clone(), count))? {
+                    Some(cards) => cards,
+                    None => continue 'count,
+                };
+
+                let action = Action::Leap { cards };
+                match back(self.confirm(game, player, &action))? {
+                    Some(true) => break 'count action,
+                    Some(false) => return Ok(()),
+                    None => continue,
+                }
+            }
+        };
+
+        match self.perform(game, action) {
+            Ok(outcome) => {
+                self.say(Message::Leapt)?;
+                self.reveal(&outcome)?;
             },
+            Err(error) => writeln!(self.output, "{}", error.localize(self.lang))?,
+        }
+        Ok(())
+    }
+
+    /// Shows the player the cards an expansion card let them see.
+    fn reveal(&mut self, outcome: &Outcome) -> io::Result<()> {
+        for card in &outcome.revealed {
+            self.say(Message::Peek(card.value, &self.style.card(card, false, self.lang)))?;
+        }
+        Ok(())
+    }
+
+    fn pass(&mut self, game: &mut Game, player: usize) -> Result<(), PromptError> {
+        let partner = match game.partner(player) {
+            Some(partner) => partner,
+            None => return Ok(self.say(Message::OnlyInTeams)?),
         };
 
-        Ok(result)
+        let action = loop {
+            let card = self.prompt_hand_selection(game, player, Message::SelectToPass, &[])?;
+            let action = Action::Pass { card };
+            match back(self.confirm(game, player, &action))? {
+                Some(true) => break action,
+                Some(false) => return Ok(()),
+                None => continue,
+            }
+        };
+
+        match self.perform(game, action) {
+            Ok(_) => self.say(Message::PartnerHasCard(partner))?,
+            Err(error) => writeln!(self.output, "{}", error.localize(self.lang))?,
+        }
+        Ok(())
     }
 
     fn print_state(&mut self, game: &Game, player: usize) -> io::Result<()> {
         const SEPARATOR: &str = "--------------------";
+        let partner = game.partner(player);
         let player = &game.players[player];
 
+        if let Some(partner) = partner {
+            let partner = &game.players[partner];
+            let hand = self.style.hand(partner, self.lang);
+            writeln!(
+                self.output,
+                "{}\n{}\n{}",
+                SEPARATOR,
+                self.lang.text(Message::Partner(partner.identity)),
+                self.lang.text(Message::PartnerHand(&hand)),
+            )?;
+        }
         write!(
             self.output,
             "{separator}\n\
-            Color: {color}\n\
-            Hand: {hand}\n\
+            {color}\n\
+            {hand}\n\
             {separator}\n\
-            Table: {table}\n\
+            {table}\n\
             {separator}\n",
             separator = SEPARATOR,
-            color = player.identity,
-            hand = player.hand,
-            table = game.table,
+            color = self.lang.text(Message::Identity(player.identity)),
+            hand = self.lang.text(Message::Hand(&self.style.hand(player, self.lang))),
+            table = self.lang.text(Message::Table(&self.style.table(&game.table, self.lang))),
         )
     }
 
-    fn prompt_bool(&mut self, message: impl fmt::Display) -> io::Result<bool> {
+    fn confirm(&mut self, game: &Game, player: usize, action: &Action) -> Result<bool, PromptError> {
+        match game.preview(player, action) {
+            Ok(preview) => {
+                write!(self.output, "{}", self.style.preview(&preview, self.lang))?;
+                self.prompt_bool(Message::GoAhead)
+            },
+            Err(error) => {
+                writeln!(self.output, "{}", error.localize(self.lang))?;
+                Ok(false)
+            },
+        }
+    }
+
+    /// Reads the answer to a prompt. Unlike an empty command line, running out of input here is an error.
+    fn read_answer(&mut self, buffer: &mut String) -> io::Result<()> {
+        if self.input.read_line(buffer)? == 0 {
+            return Err(io::Error::new(ErrorKind::UnexpectedEof, "Input ended in the middle of a prompt"));
+        }
+        Ok(())
+    }
+
+    /// Reads the answer to a step of a dialogue, which the player can also go back or cancel from.
+    fn read_step(&mut self, buffer: &mut String) -> Result<(), PromptError> {
+        self.read_answer(buffer)?;
+        match self.lang.escape(buffer.trim()) {
+            Some(escape) => Err(escape),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes `message` in the player's language, as a line of its own.
+    fn say(&mut self, message: Message) -> io::Result<()> {
+        writeln!(self.output, "{}", self.lang.text(message))
+    }
+
+    fn prompt_bool(&mut self, message: Message) -> Result<bool, PromptError> {
         let mut buffer = String::new();
+        let question = self.lang.text(message);
 
         loop {
             buffer.clear();
-            write!(self.output, "{} (yes/no) > ", message)?;
+            write!(self.output, "{}", self.lang.text(Message::YesNo(&question)))?;
             self.output.flush()?;
-            self.input.read_line(&mut buffer)?;
+            self.read_step(&mut buffer)?;
 
-            break match buffer.trim() {
-                "yes" => Ok(true),
-                "no" => Ok(false),
-                _ => {
-                    write!(self.output, "Please enter 'yes' or 'no' > ")?;
+            break match self.lang.answer(buffer.trim()) {
+                Some(answer) => Ok(answer),
+                None => {
+                    write!(self.output, "{}", self.lang.text(Message::PleaseYesNo))?;
                     continue;
                 }
             };
         }
     }
 
-    fn prompt_usize(&mut self, message: impl fmt::Display) -> io::Result<usize> {
+    fn prompt_usize(&mut self, message: impl fmt::Display) -> Result<usize, PromptError> {
         let mut buffer = String::new();
 
         loop {
             buffer.clear();
             write!(self.output, "{}\n> ", message)?;
             self.output.flush()?;
-            self.input.read_line(&mut buffer)?;
+            self.read_step(&mut buffer)?;
 
             if let Ok(r) = buffer.trim().parse::<usize>() {
                 break Ok(r);
             }
-            writeln!(self.output, "What you entered is not an unsigned integer! Please try again.")?;
+            self.say(Message::NotANumber)?;
         }
     }
 
-    fn prompt_hand_selection(&mut self, game: &Game, player: usize, message: impl fmt::Display) -> io::Result<usize> {
+    /// Asks for a card in `player`'s hand other than those already `chosen`.
+    fn prompt_hand_selection(
+        &mut self,
+        game: &Game,
+        player: usize,
+        message: Message,
+        chosen: &[usize],
+    ) -> Result<usize, PromptError> {
         let player = &game.players[player];
+        let message = self.lang.text(message);
 
         loop {
             writeln!(self.output, "{}", message)?;
-            let selection = self.prompt_usize(&player.hand)?;
+            let selection = self.prompt_usize(self.style.hand(player, self.lang))?;
 
             if selection >= player.hand.len() {
-                writeln!(self.output, "{} is not a valid selection!", selection)?;
+                self.say(Message::NotValidSelection(selection))?;
+                continue;
+            }
+            if chosen.contains(&selection) {
+                self.say(Message::AlreadyChosen(selection))?;
                 continue;
             }
 
@@ -234,14 +581,15 @@ impl<Input: BufRead, Output: Write> Ui<Input, Output> {
         }
     }
 
-    fn prompt_value_or_monad(&mut self, game: &Game) -> io::Result<ValueOrMonad> {
+    fn prompt_value_or_monad(&mut self, game: &Game) -> Result<ValueOrMonad, PromptError> {
         let mut buffer = String::new();
 
         loop {
             buffer.clear();
-            write!(self.output, "Select a deck (Common Bi Tri Quad Quint Monad) > ")?;
+            let decks = game.table.ladder.values().map(|value| format!("{:?}", value)).join(" ");
+            write!(self.output, "{}", self.lang.text(Message::SelectDeck(&decks)))?;
             self.output.flush()?;
-            self.input.read_line(&mut buffer)?;
+            self.read_step(&mut buffer)?;
 
             let trimmed = buffer.trim();
             if let Ok(monad) = trimmed.parse::<Monad>() {
@@ -249,64 +597,54 @@ impl<Input: BufRead, Output: Write> Ui<Input, Output> {
                 break Ok(ValueOrMonad::Monad);
             }
 
-            if let Ok(value) = trimmed.parse::<Value>() {
+            if let Some(value) = game.table.ladder.value(trimmed) {
                 if game.table.deck(value).is_empty() {
-                    writeln!(self.output, "That deck is out of cards!")?;
+                    self.say(Message::DeckEmpty)?;
                     continue;
                 }
 
                 break Ok(ValueOrMonad::Value(value));
             }
 
-            writeln!(self.output, "Invalid input!")?;
+            self.say(Message::InvalidInput)?;
         }
     }
 
-    fn prompt_leap(&mut self, game: &mut Game, player: usize) -> io::Result<Result<Vec<usize>, ()>> {
-        const MIN_COMMONS: usize = 4;
-        const MAX_COMMONS: usize = 6;
-
-        let player = &mut game.players[player];
+    /// Asks how many commons to leap with, out of the counts the ladder allows and no more than the
+    /// player holds.
+    fn prompt_leap_count(&mut self, game: &Game, commons: usize) -> Result<usize, PromptError> {
+        let ladder = game.table.ladder;
+        let targets = ladder
+            .leaps()
+            .filter_map(|count| ladder.leap(count).map(|value| format!("{}: {:?}", count, value)))
+            .join(", ");
 
-        let mut commons: Vec<usize> = player
-            .hand
-            .iter()
-            .enumerate()
-            .filter(|(_, card)| card.is_common())
-            .map(|(i, _)| i)
-            .collect();
-
-        if commons.len() < MIN_COMMONS {
-            writeln!(self.output, "Not enough commons to leap!")?;
-            return Ok(Err(()));
-        }
-
-        let selected_count = loop {
-            let x = self.prompt_usize(&"Enter how many commons you want to trade! (4: Tri, 5: Quad, 6: Quint)")?;
-
-            if x < MIN_COMMONS && x > MAX_COMMONS {
-                writeln!(self.output, "Invalid selection.")?;
-                continue;
+        loop {
+            let count = self.prompt_usize(self.lang.text(Message::HowManyCommons(&targets)))?;
+            if ladder.leaps().contains(&count) && count <= commons {
+                break Ok(count);
             }
+            self.say(Message::InvalidSelection)?;
+        }
+    }
 
-            break x;
-        };
-
-        if selected_count == commons.len() {
-            return Ok(Ok(commons));
+    /// Lets the player order `commons` so the first `count` of them are the ones traded in.
+    fn prompt_leap_order(
+        &mut self,
+        game: &Game,
+        player: usize,
+        mut commons: Vec<usize>,
+        count: usize,
+    ) -> Result<Vec<usize>, PromptError> {
+        if count == commons.len() {
+            return Ok(commons);
         }
 
-        write!(
-            self.output,
-            "Here are all the commons to select.\
-            The first {} cards on the left will be traded in.\n\
-            Enter the number of the card to move it left.\n\
-            Enter {} to accept selection.\n",
-            selected_count,
-            commons.len(),
-        )?;
+        let player = &game.players[player];
+        write!(self.output, "{}", self.lang.text(Message::OrderCommons(count, commons.len())))?;
         loop {
-            let card_num = self.prompt_usize(&Deck::from(player.indexes_to_cards(&commons)))?;
+            let selection = Deck::from(player.indexes_to_cards(&commons));
+            let card_num = self.prompt_usize(self.style.deck(&selection, self.lang))?;
 
             if card_num == commons.len() {
                 break;
@@ -315,11 +653,55 @@ impl<Input: BufRead, Output: Write> Ui<Input, Output> {
                 let index = card_num.saturating_sub(1);
                 commons.swap(card_num, index);
             } else {
-                writeln!(self.output, "Not a valid selection! Please try again.")?;
+                self.say(Message::TryAgain)?;
             }
         }
 
-        while commons.len() > selected_count { commons.pop(); }
-        Ok(Ok(commons))
+        commons.truncate(count);
+        Ok(commons)
+    }
+}
+
+/// Why a dialogue ended without an answer.
+#[derive(Debug)]
+pub enum PromptError {
+    /// The player asked for the previous question again.
+    Back,
+    /// The player abandoned the action.
+    Cancelled,
+    Io(io::Error),
+}
+
+impl From<io::Error> for PromptError {
+    fn from(error: io::Error) -> Self {
+        PromptError::Io(error)
+    }
+}
+
+impl fmt::Display for PromptError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::PromptError::*;
+        match self {
+            Back      => write!(fmt, "The player went back a step"),
+            Cancelled => write!(fmt, "The player cancelled"),
+            Io(error) => write!(fmt, "{}", error),
+        }
+    }
+}
+
+impl error::Error for PromptError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            PromptError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Turns a request for the previous question into `None`, so a dialogue can ask it again.
+fn back<T>(answer: Result<T, PromptError>) -> Result<Option<T>, PromptError> {
+    match answer {
+        Err(PromptError::Back) => Ok(None),
+        answer => answer.map(Some),
     }
 }
@@ -0,0 +1,157 @@
+use itertools::Itertools;
+use std::{
+    env,
+    io::{stdout, IsTerminal},
+};
+
+use super::lang::{Lang, Message};
+use crate::game::{
+    action::Preview,
+    card::{Card, Deck},
+    player::Player,
+    table::Table,
+};
+
+// Bold, italic and underlined, one for each temperature group in turn.
+const TEMP_ATTRIBUTES: [&str; 3] = ["1", "3", "4"];
+const REVERSE: &str = "7";
+const RESET: &str = "\x1b[0m";
+// Shapes for the temperature groups, for when colour can't tell them apart.
+const TEMP_MARKERS: [&str; 3] = ["△", "▽", "◇"];
+
+/// How the `Ui` draws cards.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Style {
+    /// Plain text, the same as the cards' own `Display`.
+    Plain,
+    /// Cards in their own colour, with an attribute per temperature, wilds in reverse video and
+    /// cards in a bonus pair marked with a `+`.
+    Ansi,
+    /// Every card spelled out in words, without colour codes, symbols or glyphs, for screen readers.
+    /// English comes out as plain ASCII, while Spanish keeps its accents and inverted marks.
+    ScreenReader,
+    /// Each colour's glyph and a shape for its temperature next to its name, with wilds and
+    /// bonus pairs marked in words and a `+`.
+    ColorBlind,
+}
+
+impl Style {
+    /// Colour for a terminal, and plain text when the output goes elsewhere or `NO_COLOR` is set.
+    pub fn detect() -> Self {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        if stdout().is_terminal() && !no_color {
+            Style::Ansi
+        } else {
+            Style::Plain
+        }
+    }
+
+    /// One card, highlighted if it is wild for whoever holds it.
+    pub fn card(self, card: &Card, wild: bool, lang: Lang) -> String {
+        let temp = card.temp();
+        match self {
+            Style::Plain => card.to_string(),
+            Style::Ansi => {
+                let mut codes = vec![format!("38;5;{}", card.color.hue().ansi)];
+                codes.extend(TEMP_ATTRIBUTES.get(temp.0).map(|code| code.to_string()));
+                if wild {
+                    codes.push(REVERSE.to_string());
+                }
+                format!("\x1b[{}m{}{}", codes.join(";"), card, RESET)
+            },
+            Style::ScreenReader => format!(
+                "{:?} {}{}, {}, {}{}",
+                card.value,
+                card.color,
+                card.special.map(|special| format!(" {}", special.name)).unwrap_or_default(),
+                lang.text(Message::Worth(card.num())),
+                lang.group(card.color.set().groups.get(temp.0).unwrap_or(&"unknown")),
+                if wild { format!(", {}", lang.text(Message::Wild)) } else { String::new() },
+            ),
+            Style::ColorBlind => format!(
+                "[{} {}{} {}{}]{}",
+                card.value,
+                card.color.hue().glyph,
+                card.color,
+                TEMP_MARKERS.get(temp.0).unwrap_or(&"?"),
+                card.special.map(|special| format!(" {}", special.name)).unwrap_or_default(),
+                if wild { format!(" {}", lang.text(Message::Wild)) } else { String::new() },
+            ),
+        }
+    }
+
+    fn numbered(self, i: usize, card: String, pair: bool, lang: Lang) -> String {
+        match self {
+            Style::ScreenReader => {
+                let pair = if pair { format!(", {}", lang.text(Message::InBonusPair)) } else { String::new() };
+                format!("{}: {}{}. ", lang.text(Message::CardNumber(i)), card, pair)
+            },
+            Style::Plain => format!("{}: {} ", i, card),
+            Style::Ansi | Style::ColorBlind => format!("{}: {}{} ", i, card, if pair { "+" } else { "" }),
+        }
+    }
+
+    /// Cards numbered from 0, as they are picked by.
+    pub fn deck(self, deck: &Deck, lang: Lang) -> String {
+        if deck.is_empty() && self == Style::ScreenReader {
+            return lang.text(Message::NoCards);
+        }
+        deck.iter().enumerate().map(|(i, card)| self.numbered(i, self.card(card, false, lang), false, lang)).collect()
+    }
+
+    /// `player`'s hand numbered from 0, with their wilds and the bonus pairs they can still trade picked out.
+    pub fn hand(self, player: &Player, lang: Lang) -> String {
+        if player.hand.is_empty() && self == Style::ScreenReader {
+            return lang.text(Message::NoCards);
+        }
+        (0..player.hand.len())
+            .map(|i| {
+                let card = &player.hand[i];
+                let wild = player.is_player_wild(card);
+                let pair = (0..player.hand.len()).any(|j| j != i && player.can_take_bonus(i, j));
+                self.numbered(i, self.card(card, wild, lang), pair, lang)
+            })
+            .collect()
+    }
+
+    /// The common deck's size and every other deck's cards, a line each.
+    pub fn table(self, table: &Table, lang: Lang) -> String {
+        let decks = table.ladder.values().skip(1).zip(&table.tiers);
+        let lines = if self == Style::ScreenReader {
+            let mut lines = vec![
+                lang.text(Message::CommonDeck(table.common.len())),
+                lang.text(Message::DiscardPile(&self.deck(&table.discard, lang))),
+            ];
+            lines.extend(decks.map(|(value, deck)| lang.text(Message::ValueDeck(value, &self.deck(deck, lang)))));
+            lines
+        } else {
+            let mut lines = vec![
+                format!("{:<8} {}", lang.text(Message::CommonLabel), table.common.len()),
+                format!("{:<8} {}", lang.text(Message::DiscardLabel), self.deck(&table.discard, lang)),
+            ];
+            lines.extend(decks.map(|(value, deck)| {
+                format!("{:<8} {}", format!("{:?}:", value), self.deck(deck, lang))
+            }));
+            lines
+        };
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    }
+
+    /// What an action will do, as `Preview`'s own `Display` writes it but with the cards drawn in
+    /// this style.
+    pub fn preview(self, preview: &Preview, lang: Lang) -> String {
+        let mut lines = Vec::new();
+        if !preview.drawn.is_empty() {
+            let drawn = preview.drawn.iter().map(|value| format!("{:?}", value)).join(" ");
+            lines.push(lang.text(Message::WillDraw(&drawn)));
+        }
+        if preview.monads > 0 {
+            lines.push(lang.text(Message::WillGain(preview.monads)));
+        }
+        for (card, destination) in &preview.returned {
+            lines.push(lang.text(Message::GoesTo(&self.card(card, false, lang), destination)));
+        }
+        lines.push(lang.text(Message::HandPoints(preview.points_before, preview.points_after)));
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    }
+}
@@ -0,0 +1,341 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::io::Cursor;
+
+use super::{lang::Lang, style::Style, Ui};
+use crate::{
+    game::{Game, NumPlayers},
+    puzzle::Puzzle,
+};
+
+const WORDS: [&str; 29] = [
+    "show", "draw", "flip", "trade", "buy", "auto", "leap", "end", "help", "rules", "bonus", "yes", "no",
+    "back", "cancel",
+    "Common", "Bi", "Tri", "Quad", "Quint", "Monad",
+    "0", "1", "2", "3", "4", "5", "6", "99",
+];
+
+// A ui reading `input` that checks the game after every command and draws cards as plain English text.
+fn test_ui<T: AsRef<[u8]>>(input: T) -> Ui<Cursor<T>, Vec<u8>> {
+    Ui {
+        input: Cursor::new(input),
+        output: Vec::new(),
+        paranoid: true,
+        record: None,
+        style: Style::Plain,
+        lang: Lang::English,
+    }
+}
+
+fn play(input: Vec<u8>, seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut ui = test_ui(input);
+    // Running out of input halfway through a prompt is an error, but never a panic.
+    drop(ui.play(Game::with_rng(NumPlayers::Three, &mut rng)));
+}
+
+#[test]
+fn arbitrary_bytes_never_panic() {
+    for seed in 0..200 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let length = rng.gen_range(0, 2000);
+        let input = (0..length).map(|_| rng.gen::<u8>()).collect();
+        play(input, seed);
+    }
+}
+
+#[test]
+fn arbitrary_commands_never_panic() {
+    for seed in 0..200 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut input = String::new();
+        for _ in 0..rng.gen_range(0, 3000) {
+            input += WORDS[rng.gen_range(0, WORDS.len())];
+            input += if rng.gen_range(0, 3) == 0 { " " } else { "\n" };
+        }
+        play(input.into_bytes(), seed);
+    }
+}
+
+#[test]
+fn starter_puzzles_take_every_turn_they_allow() {
+    for puzzle in Puzzle::starter_pack() {
+        assert!(puzzle.game.solve(&puzzle.goal).is_ok(), "{} can't be solved", puzzle.name);
+
+        let mut quicker = puzzle.goal.clone();
+        quicker.turns -= 1;
+        if quicker.turns > 0 {
+            assert!(puzzle.game.solve(&quicker).is_err(), "{} can be solved early", puzzle.name);
+        }
+    }
+}
+
+#[test]
+fn challenges_stop_once_the_goal_is_met() {
+    let challenge = |input: &str| {
+        let puzzle = Puzzle::starter_pack().remove(0);
+        let mut ui = test_ui(input.as_bytes());
+        ui.challenge(puzzle.game, &puzzle.goal).ok()
+    };
+
+    // Same temperature, then a draw that isn't allowed, then red for purple.
+    assert_eq!(challenge("trade\n0\n1\ndraw\ntrade\n0\n3\nno\nyes\n"), Some(true));
+    assert_eq!(challenge("trade\n0\n1\n"), Some(false));
+    assert_eq!(challenge("end\n"), Some(false));
+}
+
+#[test]
+fn tutorial_checks_each_lesson() {
+    let tutorial = |input: &str| {
+        let mut ui = test_ui(input.as_bytes());
+        ui.tutorial().ok()
+    };
+
+    let script = "trade\n0\n2\nyes\n\
+        trade\n0\n2\nyes\n\
+        trade\n0\n1\nyes\nyes\n\
+        leap\n4\nyes\n\
+        buy auto Quad\nyes\n\
+        draw\n\
+        flip\n\
+        trade\n0\n1\nyes\n";
+    assert_eq!(tutorial(script), Some(8));
+
+    // Trading the two commons in the wilds lesson works, but doesn't count.
+    assert_eq!(tutorial("trade\n0\n2\nyes\ntrade\n1\n3\nno\nyes\nskip\nquit\n"), Some(1));
+    assert_eq!(tutorial(""), Some(0));
+}
+
+#[test]
+fn rules_come_from_the_engine() {
+    use crate::game::card::STANDARD;
+
+    let mut ui = test_ui(&b"rules\nhelp trade\n"[..]);
+    drop(ui.play(Game::with_rng(NumPlayers::Two, &mut StdRng::seed_from_u64(0))));
+    let output = String::from_utf8(ui.output).unwrap();
+
+    for value in STANDARD.values() {
+        assert!(output.contains(&format!("{:?}: {} point", value, value.points())), "{:?} is missing", value);
+    }
+    assert!(output.contains(&format!("Monad: {} points", STANDARD.monad_price)));
+    for count in STANDARD.leaps() {
+        assert!(output.contains(&format!("{} commons -> a {:?}", count, STANDARD.leap(count).unwrap())));
+    }
+    assert!(output.contains("Usage: trade"));
+    assert!(output.contains("and no change is given.\n"));
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut game = Game::with_rng(NumPlayers::Two, &mut rng);
+    game.add_expansion(vec![&crate::game::expansion::REFUND], &mut rng);
+    let mut ui = test_ui(&b"rules buying\n"[..]);
+    drop(ui.play(game));
+    assert!(String::from_utf8(ui.output).unwrap().contains("a Refund card hands back what was overpaid"));
+
+    let game = Game::with_teams(1, &STANDARD, &crate::game::card::SIX_COLORS, &mut StdRng::seed_from_u64(0));
+    let mut ui = test_ui(&b"rules teams\n"[..]);
+    drop(ui.play(game));
+    assert!(String::from_utf8(ui.output).unwrap().contains("once you have passed you can't draw or flip"));
+}
+
+#[test]
+fn drafts_pick_in_reverse_seat_order() {
+    use crate::game::card::{Color, SIX_COLORS};
+
+    let input = "Blue\nBlue\nmauve\nRed\nGreen\n";
+    let mut ui = test_ui(input.as_bytes());
+    assert_eq!(ui.draft(NumPlayers::Three, &SIX_COLORS).ok(), Some(vec![Color::Green, Color::Red, Color::Blue]));
+    let output = String::from_utf8(ui.output).unwrap();
+    assert!(output.starts_with("Player 3, pick your colour"));
+    assert!(output.contains("Player 3 already took Blue!"));
+    assert!(output.contains("That isn't a colour!"));
+
+    let mut ui = test_ui(&b"Blue\n"[..]);
+    assert!(ui.draft(NumPlayers::Two, &SIX_COLORS).is_err());
+
+    let mut ui = test_ui(&b"back\nBlue\nback\nRed\nBlue\n"[..]);
+    assert_eq!(ui.draft(NumPlayers::Two, &SIX_COLORS).ok(), Some(vec![Color::Blue, Color::Red]));
+    let output = String::from_utf8(ui.output).unwrap();
+    assert!(output.contains("no pick to go back to"));
+    assert!(!output.contains("That isn't a colour!"));
+
+    let mut ui = test_ui(&b"Blue\ncancel\n"[..]);
+    assert!(matches!(ui.draft(NumPlayers::Two, &SIX_COLORS), Err(super::PromptError::Cancelled)));
+}
+
+#[test]
+fn dialogues_go_back_and_cancel_without_acting() {
+    let puzzle = Puzzle::starter_pack().remove(0);
+    // A repeated card, back to the first card, a cancelled bonus question, a cancelled buy, then back from
+    // the bonus question to the second card before trading red for purple.
+    let input = "trade\n0\n0\nback\n0\n3\ncancel\n\
+        buy\n0\nback\nback\nbuy\n0\nno\ncancel\n\
+        trade\n0\n3\nback\n3\nno\nyes\n";
+    let mut ui = test_ui(input.as_bytes());
+    assert_eq!(ui.challenge(puzzle.game, &puzzle.goal).ok(), Some(true));
+
+    let output = String::from_utf8(ui.output).unwrap();
+    assert!(output.contains("Card 0 is already chosen!"));
+    assert_eq!(output.matches("Cancelled, nothing was done.").count(), 3);
+    assert_eq!(output.matches("Please select the first card to trade!").count(), 3);
+    assert_eq!(output.matches("You traded for").count(), 1);
+}
+
+#[test]
+fn going_back_from_the_deck_asks_for_more_cards_again() {
+    use crate::game::{builder::GameBuilder, card::{Card, Color, Value}};
+
+    let game = GameBuilder::new(NumPlayers::Two)
+        .hand(0, vec![
+            Card::new(Value::Bi, Color::Red),
+            Card::new(Value::Bi, Color::Purple),
+            Card::new(Value::Common, Color::Green),
+        ])
+        .build()
+        .unwrap();
+
+    // Both Bis, back from the deck to add the common after all, then a Tri for exactly 7.
+    let mut ui = test_ui(&b"buy
+0
+yes
+1
+no
+back
+yes
+2
+Tri
+yes
+"[..]);
+    let game = ui.play(game).unwrap();
+    let output = String::from_utf8(ui.output).unwrap();
+    assert_eq!(output.matches("More cards?").count(), 3);
+    assert!(output.contains("Player bought a card!"));
+    assert!(game.players[0].hand.iter().map(|card| card.value).eq(vec![Value::Tri]));
+}
+
+#[test]
+fn leaps_ask_for_no_more_commons_than_the_hand_holds() {
+    use crate::game::{builder::GameBuilder, card::{Card, Color, Value}};
+
+    let commons = [Color::Red, Color::Orange, Color::Yellow, Color::Green, Color::Blue];
+    let game = GameBuilder::new(NumPlayers::Two)
+        .hand(0, commons.iter().map(|&color| Card::new(Value::Common, color)).collect())
+        .build()
+        .unwrap();
+
+    let mut ui = test_ui(&b"leap\n6\n5\nyes\n"[..]);
+    let game = ui.play(game).unwrap();
+    let output = String::from_utf8(ui.output).unwrap();
+    assert!(output.contains("Invalid selection."));
+    assert!(output.contains("Player leapt ahead and drew a card!"));
+    assert_eq!(game.players[0].hand.len(), 1);
+}
+
+#[test]
+fn ansi_style_colours_cards_and_marks_wilds_and_pairs() {
+    use super::style::Style;
+    use crate::game::{builder::GameBuilder, card::{Card, Color, Value}, expansion::PRISM, rules::Rules};
+
+    let game = GameBuilder::new(NumPlayers::Two)
+        .identity(0, Color::Blue)
+        .hand(0, vec![
+            Card::new(Value::Tri, Color::Red),
+            Card::new(Value::Tri, Color::Purple),
+            Card::new(Value::Bi, Color::Blue),
+        ])
+        .build()
+        .unwrap();
+    let player = &game.players[0];
+
+    assert_eq!(Style::Plain.hand(player, Lang::English), player.hand.to_string());
+    assert_eq!(Style::Plain.table(&game.table, Lang::English), game.table.to_string());
+
+    let hand = Style::Ansi.hand(player, Lang::English);
+    assert!(hand.starts_with("0: \x1b[38;5;196;1m[③ (7) Red]\x1b[0m+ 1: \x1b[38;5;129;3m"));
+    assert!(hand.contains("\x1b[38;5;33;3;7m[② (3) Blue]\x1b[0m "));
+
+    // Only pairs that can still be traded for a bonus are marked, and a Prism pairs with anything.
+    let mut bonus_taken = player.clone();
+    bonus_taken.took_bonus = true;
+    assert!(!Style::Ansi.hand(&bonus_taken, Lang::English).contains("m+"));
+    let prism = GameBuilder::new(NumPlayers::Two)
+        .rules(Rules { expansion: vec![&PRISM], ..Rules::default() })
+        .hand(0, vec![
+            Card { special: Some(&PRISM), ..Card::new(Value::Bi, Color::Green) },
+            Card::new(Value::Bi, Color::Yellow),
+        ])
+        .build()
+        .unwrap();
+    assert_eq!(Style::Ansi.hand(&prism.players[0], Lang::English).matches("m+").count(), 2);
+}
+
+#[test]
+fn screen_reader_and_colour_blind_styles_name_every_card() {
+    use super::style::Style;
+    use crate::game::{builder::GameBuilder, card::{Card, Color, Value}};
+
+    let game = GameBuilder::new(NumPlayers::Two)
+        .identity(0, Color::Blue)
+        .hand(0, vec![
+            Card::new(Value::Tri, Color::Red),
+            Card::new(Value::Tri, Color::Purple),
+            Card::new(Value::Bi, Color::Blue),
+        ])
+        .build()
+        .unwrap();
+    let player = &game.players[0];
+
+    let hand = Style::ScreenReader.hand(player, Lang::English);
+    assert!(hand.is_ascii());
+    assert!(hand.starts_with("card 0: Tri Red, worth 7, warm, in a bonus pair. "));
+    assert!(hand.contains("card 2: Bi Blue, worth 3, cold, wild. "));
+    let table = Style::ScreenReader.table(&game.table, Lang::English);
+    assert!(table.is_ascii());
+    assert!(table.starts_with("Common deck: "));
+    // Spanish can't be ASCII, but still has no symbols for a screen reader to trip over.
+    let hand = Style::ScreenReader.hand(player, Lang::Spanish);
+    assert!(!hand.chars().any(|c| "①②③④⑤⑥♥♦★♣♠●△▽◇[]".contains(c)), "{}", hand);
+
+    let hand = Style::ColorBlind.hand(player, Lang::English);
+    assert!(hand.starts_with("0: [③ (7) ♥Red △]+ 1: [③ (7) ♣Purple ▽]+ "));
+    assert!(hand.contains("[② (3) ♠Blue ▽] wild "));
+}
+
+#[test]
+fn spanish_covers_prompts_errors_and_answers() {
+    assert_eq!(Lang::from_code("es_ES.UTF-8"), Some(Lang::Spanish));
+    assert_eq!(Lang::from_code("en"), Some(Lang::English));
+    assert_eq!(Lang::from_code("C"), None);
+
+    // Same temperature, then red for purple answered in Spanish.
+    let puzzle = Puzzle::starter_pack().remove(0);
+    let mut ui = test_ui("trade\n0\n1\nhelp draw\ntrade\n0\n3\nno\nsí\n".as_bytes());
+    ui.lang = Lang::Spanish;
+    assert_eq!(ui.challenge(puzzle.game, &puzzle.goal).ok(), Some(true));
+    let output = String::from_utf8(ui.output).unwrap();
+    assert!(output.contains("¡Elige la primera carta para intercambiar!"));
+    assert!(output.contains("Las cartas 0 y 1 son de la misma temperatura"));
+    assert!(output.contains("Uso: draw\nRoba una común."));
+    assert!(output.contains("¿Adelante? (sí/no) > "));
+    assert!(output.contains("¡Resuelto!"));
+    assert!(!output.contains("Please"));
+}
+
+#[test]
+fn games_that_end_mid_turn_show_the_results() {
+    use crate::game::{builder::GameBuilder, card::{Card, Color, Value}, table::MONADS};
+
+    // Trading the two Quints takes the last monad without ending the turn.
+    let game = GameBuilder::new(NumPlayers::Two)
+        .hand(0, vec![Card::new(Value::Quint, Color::Red), Card::new(Value::Quint, Color::Blue)])
+        .monads(1, MONADS - 1)
+        .build()
+        .unwrap();
+    let mut ui = test_ui(&b"trade\n0\n1\nyes\nshow\n"[..]);
+    let game = ui.play(game).unwrap();
+    assert!(game.is_over());
+
+    let output = String::from_utf8(ui.output).unwrap();
+    assert!(output.contains("You traded for a monad!"));
+    assert!(output.contains("The game is over! All the monads are taken."));
+    assert!(output.contains("Player 2 wins!"));
+    assert!(!output.contains("Hand:"), "no more commands are read once the game is over");
+}
@@ -0,0 +1,257 @@
+use std::io::{self, BufRead, Write};
+
+use super::{
+    lang::{Message, Text},
+    Ui,
+};
+use crate::game::{
+    builder::GameBuilder,
+    card::{Card, Color, Value},
+    notation::Move,
+    record::Record,
+    Game,
+    NumPlayers,
+};
+
+/// One rule, taught with a scripted hand that the player has to use the right way.
+struct Lesson {
+    title: Text,
+    explanation: Text,
+    task: Text,
+    identity: Color,
+    hand: Vec<Card>,
+    /// Whether the common deck starts empty, with its cards in the discard pile.
+    flipped: bool,
+    /// Whether a move is the one being taught.
+    taught: fn(&Move) -> bool,
+}
+
+impl Lesson {
+    fn game(&self) -> Game {
+        let mut builder = GameBuilder::new(NumPlayers::Two)
+            .identity(0, self.identity)
+            .identity(1, self.identity.bonus_partner())
+            .hand(0, self.hand.clone());
+        if self.flipped {
+            builder = builder.deck(Value::Common, Vec::new());
+        }
+        builder.build().expect("Tutorial hands should be consistent")
+    }
+}
+
+fn card(value: Value, color: Color) -> Card {
+    Card::new(value, color)
+}
+
+fn lessons() -> Vec<Lesson> {
+    vec![
+        Lesson {
+            title: ["Trading", "Intercambiar"],
+            explanation: [
+                "Cards come in five values: Common (1 point), Bi (3), Tri (7), Quad (16) and Quint (36).\n\
+                Red, orange and yellow cards are warm; purple, blue and green ones are cold.\n\
+                Two cards of the same value, one warm and one cold, trade for a card of the next value up.",
+                "Las cartas tienen cinco valores: Common (1 punto), Bi (3), Tri (7), Quad (16) y Quint (36).\n\
+                Las rojas, naranjas y amarillas son cálidas; las moradas, azules y verdes son frías.\n\
+                Dos cartas del mismo valor, una cálida y otra fría, se intercambian por una del valor siguiente.",
+            ],
+            task: [
+                "Trade the red common and the blue common for a Bi.",
+                "Intercambia la común roja y la común azul por un Bi.",
+            ],
+            identity: Color::Green,
+            hand: vec![
+                card(Value::Common, Color::Red),
+                card(Value::Common, Color::Orange),
+                card(Value::Common, Color::Blue),
+                card(Value::Common, Color::Yellow),
+            ],
+            flipped: false,
+            taught: |played| matches!(played, Move::Trade { .. }),
+        },
+        Lesson {
+            title: ["Wilds", "Comodines"],
+            explanation: [
+                "Your identity colour is wild: a Bi or better in your colour can stand in for any value.\n\
+                Traded with a card of the other temperature, it counts as that card's value.\n\
+                Commons are never wild, even in your colour.",
+                "Tu color de identidad es comodín: un Bi o más de tu color puede hacer de cualquier valor.\n\
+                Intercambiado con una carta de la otra temperatura, cuenta como el valor de esa carta.\n\
+                Las comunes nunca son comodines, ni siquiera de tu color.",
+            ],
+            task: [
+                "You are blue. Trade the red Tri and the blue Bi for a Quad.",
+                "Eres azul. Intercambia el Tri rojo y el Bi azul por un Quad.",
+            ],
+            identity: Color::Blue,
+            hand: vec![
+                card(Value::Tri, Color::Red),
+                card(Value::Common, Color::Blue),
+                card(Value::Bi, Color::Blue),
+                card(Value::Common, Color::Orange),
+            ],
+            flipped: false,
+            taught: |played| match played {
+                Move::Trade { cards, .. } => cards[0].card.value != cards[1].card.value,
+                _ => false,
+            },
+        },
+        Lesson {
+            title: ["Bonus pairs", "Parejas de bonificación"],
+            explanation: [
+                "Red and purple, orange and blue, and yellow and green are bonus pairs.\n\
+                Trading a bonus pair can also earn a card of every lower value, once a turn.\n\
+                Wilds can't be used for a bonus.",
+                "Rojo y morado, naranja y azul, y amarillo y verde son parejas de bonificación.\n\
+                Intercambiar una pareja de bonificación también da una carta de cada valor inferior,\n\
+                una vez por turno. Los comodines no sirven para una bonificación.",
+            ],
+            task: [
+                "Trade the red Bi and the purple Bi, and take the bonus.",
+                "Intercambia el Bi rojo y el Bi morado, y llévate la bonificación.",
+            ],
+            identity: Color::Green,
+            hand: vec![card(Value::Bi, Color::Red), card(Value::Bi, Color::Purple), card(Value::Common, Color::Yellow)],
+            flipped: false,
+            taught: |played| matches!(played, Move::Trade { bonus: true, .. }),
+        },
+        Lesson {
+            title: ["Leaping", "Saltar"],
+            explanation: [
+                "Commons that can't be traded can still leap:\n\
+                four commons make a Tri, five a Quad and six a Quint.",
+                "Las comunes que no se pueden intercambiar aún pueden saltar:\n\
+                cuatro comunes dan un Tri, cinco un Quad y seis un Quint.",
+            ],
+            task: ["Leap with four commons for a Tri.", "Salta con cuatro comunes a un Tri."],
+            identity: Color::Red,
+            hand: vec![
+                card(Value::Common, Color::Red),
+                card(Value::Common, Color::Orange),
+                card(Value::Common, Color::Yellow),
+                card(Value::Common, Color::Red),
+            ],
+            flipped: false,
+            taught: |played| matches!(played, Move::Leap { .. }),
+        },
+        Lesson {
+            title: ["Buying", "Comprar"],
+            explanation: [
+                "Cards can also be bought with points, as long as every card paid with is worth less\n\
+                than what it buys. Change is not given back.",
+                "Las cartas también se pueden comprar con puntos, siempre que cada carta con la que pagas valga\n\
+                menos que lo que compra. No se da cambio.",
+            ],
+            task: [
+                "Buy a Quad. 'buy auto Quad' picks the cheapest payment for you.",
+                "Compra un Quad. 'buy auto Quad' elige por ti el pago más barato.",
+            ],
+            identity: Color::Green,
+            hand: vec![card(Value::Tri, Color::Red), card(Value::Tri, Color::Orange), card(Value::Bi, Color::Yellow)],
+            flipped: false,
+            taught: |played| matches!(played, Move::Buy { .. }),
+        },
+        Lesson {
+            title: ["Drawing", "Robar"],
+            explanation: [
+                "Instead of trading, buying or leaping, you can draw a common. That ends your turn.",
+                "En lugar de intercambiar, comprar o saltar, puedes robar una común. Eso termina tu turno.",
+            ],
+            task: ["Draw a common.", "Roba una común."],
+            identity: Color::Green,
+            hand: vec![card(Value::Common, Color::Red), card(Value::Common, Color::Orange)],
+            flipped: false,
+            taught: |played| matches!(played, Move::Draw),
+        },
+        Lesson {
+            title: ["Flipping", "Dar la vuelta"],
+            explanation: [
+                "Spent commons go to the discard pile. Once the common deck runs out,\n\
+                the discard pile can be flipped over to make a new one. That ends your turn too.",
+                "Las comunes gastadas van a la pila de descarte. Cuando se acaba el mazo común,\n\
+                se le puede dar la vuelta al descarte para hacer uno nuevo. Eso también termina tu turno.",
+            ],
+            task: ["Flip the discard pile.", "Dale la vuelta a la pila de descarte."],
+            identity: Color::Green,
+            hand: vec![card(Value::Common, Color::Red), card(Value::Common, Color::Orange)],
+            flipped: true,
+            taught: |played| matches!(played, Move::Flip),
+        },
+        Lesson {
+            title: ["Monads", "Mónadas"],
+            explanation: [
+                "Two Quints of different temperatures trade for a monad, and 80 points buy one.\n\
+                The game ends when the monad pile is empty. Whoever holds the most monads wins.",
+                "Dos Quint de temperaturas distintas se intercambian por una mónada, y 80 puntos compran una.\n\
+                La partida termina cuando se acaban las mónadas. Gana quien tenga más.",
+            ],
+            task: ["Trade your Quints for a monad.", "Intercambia tus Quint por una mónada."],
+            identity: Color::Green,
+            hand: vec![card(Value::Quint, Color::Red), card(Value::Quint, Color::Blue)],
+            flipped: false,
+            taught: |played| matches!(played, Move::Trade { monad: true, .. }),
+        },
+    ]
+}
+
+impl<Input: BufRead, Output: Write> Ui<Input, Output> {
+    /// Walks through the rules one lesson at a time, handing back how many lessons were finished.
+    pub fn tutorial(&mut self) -> io::Result<usize> {
+        let lessons = lessons();
+        let saved = self.record.take();
+        let mut finished = 0;
+
+        for (i, lesson) in lessons.iter().enumerate() {
+            self.say(Message::Lesson(i, lessons.len(), self.lang.pick(&lesson.title)))?;
+            writeln!(self.output, "{}", self.lang.pick(&lesson.explanation))?;
+            match self.lesson(lesson)? {
+                Some(true) => finished += 1,
+                Some(false) => {},
+                None => break,
+            }
+        }
+
+        self.record = saved;
+        Ok(finished)
+    }
+
+    /// Plays one lesson until the taught move is made: `Some(false)` if it was skipped, or `None` if
+    /// the player quit.
+    fn lesson(&mut self, lesson: &Lesson) -> io::Result<Option<bool>> {
+        let mut buffer = String::new();
+        let mut game = lesson.game();
+        self.record = Some(Record::new(&game));
+        self.print_state(&game, 0)?;
+
+        loop {
+            buffer.clear();
+            let task = self.lang.pick(&lesson.task);
+            write!(self.output, "{}", self.lang.text(Message::LessonActions(task, &super::help::command_names())))?;
+            self.output.flush()?;
+            if self.input.read_line(&mut buffer)? == 0 {
+                return Ok(None);
+            }
+
+            match buffer.split_whitespace().collect::<Vec<_>>().as_slice() {
+                ["skip"] => return Ok(Some(false)),
+                ["quit"] => return Ok(None),
+                _ => self.command(&mut game, 0, &buffer)?,
+            }
+
+            let played = self.record.as_mut().and_then(|record| record.turns.pop()).and_then(|mut turn| turn.pop());
+            match played {
+                Some(played) if (lesson.taught)(&played) => {
+                    self.say(Message::WellDone)?;
+                    return Ok(Some(true));
+                },
+                Some(_) => {
+                    self.say(Message::NotThisLesson)?;
+                    game = lesson.game();
+                    self.record = Some(Record::new(&game));
+                    self.print_state(&game, 0)?;
+                },
+                None => {},
+            }
+        }
+    }
+}